@@ -0,0 +1,52 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput, Data, Fields};
+
+pub fn derive_from_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+
+    // Get the struct fields
+    let fields = match input.data {
+        Data::Struct(data) => match data.fields {
+            Fields::Named(fields) => fields.named,
+            _ => panic!("FromRow derive only works on structs with named fields"),
+        },
+        _ => panic!("FromRow derive only works on structs"),
+    };
+
+    // Each field reads the column with the same name, unless renamed with #[column("alias")]
+    let mut field_inits = Vec::new();
+    let mut field_types = Vec::new();
+
+    for field in fields.iter() {
+        let field_name = field.ident.clone().unwrap();
+        let field_type = &field.ty;
+
+        let column = field.attrs.iter()
+            .find(|attr| attr.path().is_ident("column"))
+            .map(|attr| attr.parse_args::<syn::LitStr>().unwrap().value())
+            .unwrap_or_else(|| field_name.to_string());
+
+        field_inits.push(quote! {
+            #field_name: row.get::<#field_type>(#column)?
+        });
+        field_types.push(field_type.clone());
+    }
+
+    let expanded = quote! {
+        impl ::okodata::FromRow for #name {
+            fn from_row(row: &::okodata::Row) -> ::okodata::Result<Self> {
+                Ok(Self {
+                    #(#field_inits),*
+                })
+            }
+
+            fn register_keys(keys: &mut ::okodata::PrimaryKeys) {
+                #(<#field_types as ::okodata::FromValue>::register_keys(keys);)*
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
@@ -1,24 +1,39 @@
 extern crate proc_macro;
 
+mod from_row;
 mod node;
 mod relationship;
+mod validate;
 
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::{parse_macro_input, LitStr};
 
 /// Derive macro for Node types
-#[proc_macro_derive(Node, attributes(label, id, property))]
+///
+/// `#[index]` is reserved and rejected at compile time, since Kuzu 0.7.1 only
+/// indexes primary keys; use `#[unique]` or `#[fulltext]` instead.
+#[proc_macro_derive(Node, attributes(label, id, property, relation, fulltext, vector, unique, index, validate))]
 pub fn derive_node(input: TokenStream) -> TokenStream {
     node::derive_node(input)
 }
 
 /// Derive macro for Relationship types
-#[proc_macro_derive(Relationship, attributes(label, from_node, to_node, property))]
+///
+/// Mark the endpoint fields with the node types they refer to, as in
+/// `#[from_node(User)]` and `#[to_node(Post)]`. Each endpoint field holds a value
+/// that converts into its node type's primary key.
+#[proc_macro_derive(Relationship, attributes(label, from_node, to_node, property, validate))]
 pub fn derive_relationship(input: TokenStream) -> TokenStream {
     relationship::derive_relationship(input)
 }
 
+/// Derive macro for mapping query result rows onto structs by column alias
+#[proc_macro_derive(FromRow, attributes(column))]
+pub fn derive_from_row(input: TokenStream) -> TokenStream {
+    from_row::derive_from_row(input)
+}
+
 /// Attribute macro for marking a field as a from_node
 #[proc_macro_attribute]
 pub fn from_node(_attr: TokenStream, item: TokenStream) -> TokenStream {
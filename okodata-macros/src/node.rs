@@ -1,10 +1,12 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput, Data, Fields};
+use syn::{parse_macro_input, DeriveInput, Data, Fields, Meta, Token};
+use syn::punctuated::Punctuated;
 
 pub fn derive_node(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = input.ident;
+    let vis = input.vis;
     
     // Get the struct fields
     let fields = match input.data {
@@ -16,23 +18,80 @@ pub fn derive_node(input: TokenStream) -> TokenStream {
     };
 
     // Find the primary key field and collect property fields
-    let mut primary_key_field = None;
+    let mut id_fields = Vec::new();
+    let mut key_generation = None;
+    let mut column_defaults = Vec::new();
+    let mut read_defaults = Vec::new();
     let mut property_fields = Vec::new();
+    let mut relation_fields = Vec::new();
+    let mut field_consts = Vec::new();
+    let mut fulltext_fields = Vec::new();
+    let mut vector_fields = Vec::new();
+    let mut indexes = Vec::new();
+    let mut validations = Vec::new();
     
     for field in fields.iter() {
         let field_name = field.ident.clone().unwrap();
         let field_type = &field.ty;
+        validations.extend(crate::validate::field_checks(field));
         
         for attr in &field.attrs {
             if attr.path().is_ident("id") {
-                primary_key_field = Some(field_name.clone());
+                if let Some(generation) = parse_id(attr, field_type) {
+                    key_generation = Some(generation);
+                }
+                id_fields.push((field_name.clone(), field_type.clone()));
+                field_consts.push((field_name.clone(), field_type.clone()));
             } else if attr.path().is_ident("property") {
+                if let Some(default) = parse_property_default(attr) {
+                    column_defaults.push((field_name.to_string(), default));
+                    read_defaults.push((field_name.to_string(), quote! { <#field_type as ::std::default::Default>::default() }));
+                }
                 property_fields.push((field_name.clone(), field_type.clone()));
+                field_consts.push((field_name.clone(), field_type.clone()));
+            } else if attr.path().is_ident("fulltext") {
+                if quote!(#field_type).to_string() != "String" {
+                    panic!("#[fulltext] is only supported on String fields");
+                }
+                fulltext_fields.push(field_name.to_string());
+            } else if attr.path().is_ident("index") {
+                let message = "#[index] is not supported: Kuzu 0.7.1 cannot build secondary or full-text indexes and only indexes primary keys; \
+                               use #[unique] for a constraint enforced on write, or #[fulltext] for keyword search";
+                return syn::Error::new_spanned(attr, message).to_compile_error().into();
+            } else if attr.path().is_ident("unique") {
+                indexes.push((field_name.to_string(), quote! { ::okodata::IndexKind::Unique }));
+            } else if attr.path().is_ident("vector") {
+                vector_fields.push((field_name.to_string(), parse_vector(attr, field_type)));
+                field_consts.push((field_name.clone(), field_type.clone()));
+            } else if attr.path().is_ident("relation") {
+                relation_fields.push((field_name.clone(), parse_relation(attr)));
+            }
+        }
+
+        // Searched and unique fields are stored even without #[property]
+        let has = |name: &str| field.attrs.iter().any(|a| a.path().is_ident(name));
+        if (has("fulltext") || has("unique")) && !has("property") && !has("id") {
+            property_fields.push((field_name.clone(), field_type.clone()));
+            field_consts.push((field_name.clone(), field_type.clone()));
+        }
+
+        // Properties with #[serde(default)] are read as that default when null
+        if has("property") || has("unique") || has("fulltext") {
+            if let Some(default) = serde_default(field) {
+                if !read_defaults.iter().any(|(name, _)| field_name == name) {
+                    read_defaults.push((field_name.to_string(), default));
+                }
             }
         }
     }
 
-    let primary_key_field = primary_key_field.expect("Node must have a field marked with #[id]");
+    if id_fields.is_empty() {
+        panic!("Node must have a field marked with #[id]");
+    }
+    let composite = id_fields.len() > 1;
+    if composite && key_generation.is_some() {
+        panic!("#[id(auto = \"..\")] cannot be combined with other #[id] fields");
+    }
 
     // Get the label from attributes or use struct name
     let label = input.attrs.iter()
@@ -40,12 +99,12 @@ pub fn derive_node(input: TokenStream) -> TokenStream {
         .map(|attr| attr.parse_args::<syn::LitStr>().unwrap().value())
         .unwrap_or_else(|| name.to_string());
 
-    // Generate property type initialization code
-    let property_type_inits = property_fields.iter().map(|(name, ty)| {
+    // Generate property type initialization code; every key part is stored as a property
+    let property_type_inits = id_fields.iter().chain(&property_fields).map(|(name, ty)| {
         let name_str = name.to_string();
         quote! {
             props.insert(#name_str.to_string(), match stringify!(#ty) {
-                "String" | "&str" => serde_json::Value::String(String::new()),
+                "String" | "&str" | "Uuid" | "uuid::Uuid" | "okodata::Uuid" => serde_json::Value::String(String::new()),
                 "i64" | "i32" | "i16" | "i8" | "u64" | "u32" | "u16" | "u8" => serde_json::Value::Number(0.into()),
                 "bool" => serde_json::Value::Bool(false),
                 _ => serde_json::Value::Null,
@@ -53,28 +112,319 @@ pub fn derive_node(input: TokenStream) -> TokenStream {
         }
     });
 
+    // A single key field is the primary key; several are combined into a synthetic key column
+    let id_names: Vec<_> = id_fields.iter().map(|(name, _)| name).collect();
+    let id_types: Vec<_> = id_fields.iter().map(|(_, ty)| ty).collect();
+    let key_methods = if composite {
+        quote! {
+            type Key = (#(#id_types,)*);
+
+            fn primary_key_field() -> &'static str {
+                ::okodata::COMPOSITE_KEY_FIELD
+            }
+
+            fn primary_key(&self) -> Self::Key {
+                (#(self.#id_names.clone(),)*)
+            }
+        }
+    } else {
+        let (name, ty) = &id_fields[0];
+        quote! {
+            type Key = #ty;
+
+            fn primary_key_field() -> &'static str {
+                stringify!(#name)
+            }
+
+            fn primary_key(&self) -> Self::Key {
+                self.#name.clone()
+            }
+        }
+    };
+    let key_generation = match key_generation {
+        Some(generation) => quote! {
+            fn key_generation() -> Option<::okodata::KeyGeneration> {
+                Some(#generation)
+            }
+        },
+        None => quote! {},
+    };
+    let composite_key_type = if composite {
+        quote! { props.insert(::okodata::COMPOSITE_KEY_FIELD.to_string(), serde_json::Value::String(String::new())); }
+    } else {
+        quote! {}
+    };
+
+    // Relation fields are loaded on demand rather than stored as properties
+    let properties_method = if relation_fields.is_empty() && !composite {
+        quote! {}
+    } else {
+        let names = relation_fields.iter().map(|(name, _)| name.to_string());
+        let composite_key = if composite {
+            quote! {
+                obj.insert(
+                    ::okodata::COMPOSITE_KEY_FIELD.to_string(),
+                    serde_json::Value::String(::okodata::KeyType::to_key_string(&self.primary_key())),
+                );
+            }
+        } else {
+            quote! {}
+        };
+        quote! {
+            fn properties(&self) -> serde_json::Value {
+                let mut props = serde_json::to_value(self).unwrap();
+                if let serde_json::Value::Object(obj) = &mut props {
+                    #(obj.remove(#names);)*
+                    #composite_key
+                }
+                props
+            }
+        }
+    };
+    let relation_methods = if relation_fields.is_empty() {
+        quote! {}
+    } else {
+        let binds = relation_fields.iter().map(|(name, (rel, direction))| {
+            quote! {
+                self.#name.bind::<#rel, Self>(key.clone(), #direction);
+            }
+        });
+        quote! {
+            fn bind_relations(&mut self) {
+                let key = ::okodata::KeyType::to_value(&self.primary_key());
+                #(#binds)*
+            }
+        }
+    };
+
+    // Typed field references for projections, e.g. `UserFields::name`, kept on a
+    // companion type so they cannot clash with methods of the node
+    let fields_type = syn::Ident::new(&format!("{}Fields", name), name.span());
+    let fields_doc = format!("Typed references to the properties of [`{}`]", name);
+    let field_consts = field_consts.iter().map(|(field, ty)| {
+        quote! {
+            #vis const #field: ::okodata::Field<#name, #ty> = ::okodata::Field::new(stringify!(#field));
+        }
+    });
+
+    let validate_method = crate::validate::validate_method(&validations);
+    let default_entries = column_defaults.iter().map(|(name, default)| quote! { (#name, #default) });
+    let default_names = read_defaults.iter().map(|(name, _)| name);
+    let default_values = read_defaults.iter().map(|(_, value)| value);
+    let index_entries = indexes.iter().map(|(name, kind)| quote! { (#name, #kind) });
+    let vector_names = vector_fields.iter().map(|(name, _)| name);
+    let vector_entries = vector_fields.iter().map(|(name, dim)| quote! { (#name, #dim) });
+
     let expanded = quote! {
         impl Node for #name {
             fn label() -> &'static str {
                 #label
             }
 
-            fn primary_key_field() -> &'static str {
-                stringify!(#primary_key_field)
+            #key_methods
+
+            #key_generation
+
+            fn column_defaults() -> Vec<(&'static str, &'static str)> {
+                vec![#(#default_entries),*]
             }
 
-            fn primary_key(&self) -> String {
-                self.#primary_key_field.to_string()
+            fn default_properties() -> serde_json::Map<String, serde_json::Value> {
+                let mut defaults = serde_json::Map::new();
+                #(defaults.insert(
+                    #default_names.to_string(),
+                    serde_json::to_value(#default_values).unwrap(),
+                );)*
+                defaults
             }
 
             fn property_types() -> serde_json::Value {
                 let mut props = std::collections::HashMap::new();
-                props.insert(stringify!(#primary_key_field).to_string(), serde_json::Value::String(String::new()));
+                #composite_key_type
                 #(#property_type_inits)*
+                #(props.insert(#vector_names.to_string(), serde_json::Value::Array(Vec::new()));)*
                 serde_json::Value::Object(serde_json::Map::from_iter(props))
             }
+
+            fn fulltext_fields() -> Vec<&'static str> {
+                vec![#(#fulltext_fields),*]
+            }
+
+            fn vector_fields() -> Vec<(&'static str, usize)> {
+                vec![#(#vector_entries),*]
+            }
+
+            fn indexes() -> Vec<(&'static str, ::okodata::IndexKind)> {
+                vec![#(#index_entries),*]
+            }
+
+            #validate_method
+
+            #properties_method
+
+            #relation_methods
+        }
+
+        impl ::okodata::FromValue for #name {
+            fn from_value(value: &::okodata::Value, _row: &::okodata::Row) -> ::okodata::Result<Self> {
+                ::okodata::node_from_value::<Self>(value)
+            }
+
+            fn register_keys(keys: &mut ::okodata::PrimaryKeys) {
+                keys.register::<Self>();
+            }
+        }
+
+        #[doc = #fields_doc]
+        #[derive(Debug, Clone, Copy)]
+        #vis struct #fields_type;
+
+        #[allow(non_upper_case_globals)]
+        impl #fields_type {
+            #(#field_consts)*
         }
     };
 
     TokenStream::from(expanded)
-} 
\ No newline at end of file
+}
+
+/// Parses `#[relation(Type, direction = "out")]` into the relationship type and direction
+fn parse_relation(attr: &syn::Attribute) -> (syn::Path, proc_macro2::TokenStream) {
+    let args = attr
+        .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+        .expect("Expected #[relation(Type, direction = \"out\")]");
+
+    let mut relationship = None;
+    let mut direction = quote! { ::okodata::Direction::Outgoing };
+    for arg in args {
+        match arg {
+            Meta::Path(path) => relationship = Some(path),
+            Meta::NameValue(nv) if nv.path.is_ident("direction") => {
+                let value = match nv.value {
+                    syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) => s.value(),
+                    _ => panic!("relation direction must be a string"),
+                };
+                direction = match value.as_str() {
+                    "out" => quote! { ::okodata::Direction::Outgoing },
+                    "in" => quote! { ::okodata::Direction::Incoming },
+                    "both" => quote! { ::okodata::Direction::Both },
+                    other => panic!("Unknown relation direction {:?}, expected \"out\", \"in\" or \"both\"", other),
+                };
+            },
+            _ => panic!("Unexpected argument in #[relation(...)]"),
+        }
+    }
+
+    let relationship = relationship.expect("#[relation(...)] must name a relationship type");
+    (relationship, direction)
+}
+
+/// Parses `#[vector(dim = N)]` on a `Vec<f32>` or `[f32; N]` field into its dimension
+fn parse_vector(attr: &syn::Attribute, ty: &syn::Type) -> usize {
+    let is_f32 = |ty: &syn::Type| quote!(#ty).to_string() == "f32";
+    let supported = match ty {
+        syn::Type::Array(array) => is_f32(&array.elem),
+        syn::Type::Path(path) => path.path.segments.last().is_some_and(|seg| {
+            seg.ident == "Vec" && matches!(&seg.arguments, syn::PathArguments::AngleBracketed(args)
+                if matches!(args.args.first(), Some(syn::GenericArgument::Type(t)) if is_f32(t)))
+        }),
+        _ => false,
+    };
+    if !supported {
+        panic!("#[vector] is only supported on Vec<f32> and [f32; N] fields");
+    }
+
+    let args = attr
+        .parse_args_with(Punctuated::<syn::MetaNameValue, Token![,]>::parse_terminated)
+        .expect("Expected #[vector(dim = N)]");
+    args.iter()
+        .find(|nv| nv.path.is_ident("dim"))
+        .and_then(|nv| match &nv.value {
+            syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(dim), .. }) => dim.base10_parse().ok(),
+            _ => None,
+        })
+        .expect("#[vector(...)] requires an integer dim")
+}
+
+/// Parses `#[id(auto = "serial")]` or `#[id(auto = "uuid")]` into how the key is generated
+fn parse_id(attr: &syn::Attribute, ty: &syn::Type) -> Option<proc_macro2::TokenStream> {
+    if matches!(attr.meta, Meta::Path(_)) {
+        return None;
+    }
+
+    let args = attr
+        .parse_args_with(Punctuated::<syn::MetaNameValue, Token![,]>::parse_terminated)
+        .expect("Expected #[id] or #[id(auto = \"serial\")]");
+    let auto = args.iter()
+        .find(|nv| nv.path.is_ident("auto"))
+        .map(|nv| match &nv.value {
+            syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) => s.value(),
+            _ => panic!("#[id(auto = ..)] expects a string"),
+        })
+        .expect("Expected #[id(auto = \"serial\")] or #[id(auto = \"uuid\")]");
+
+    let ty = quote!(#ty).to_string();
+    match auto.as_str() {
+        "serial" if ty == "i64" => Some(quote! { ::okodata::KeyGeneration::Serial }),
+        "serial" => panic!("#[id(auto = \"serial\")] is only supported on i64 fields"),
+        "uuid" if ty.ends_with("Uuid") => Some(quote! { ::okodata::KeyGeneration::Uuid }),
+        "uuid" => panic!("#[id(auto = \"uuid\")] is only supported on Uuid fields"),
+        other => panic!("Unknown key generation {:?}, expected \"serial\" or \"uuid\"", other),
+    }
+}
+
+/// Parses the Cypher expression of `#[property(default = ..)]`
+///
+/// Numbers and booleans are used as they are; strings are taken as a Cypher
+/// expression, such as `"now()"` or `"'guest'"` for a string literal.
+fn parse_property_default(attr: &syn::Attribute) -> Option<String> {
+    if matches!(attr.meta, Meta::Path(_)) {
+        return None;
+    }
+
+    let args = attr
+        .parse_args_with(Punctuated::<syn::MetaNameValue, Token![,]>::parse_terminated)
+        .expect("Expected #[property] or #[property(default = ..)]");
+    let default = args.iter()
+        .find(|nv| nv.path.is_ident("default"))
+        .expect("Expected #[property(default = ..)]");
+
+    Some(match &default.value {
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) => s.value(),
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(_) | syn::Lit::Float(_) | syn::Lit::Bool(_), .. })
+        | syn::Expr::Unary(syn::ExprUnary { op: syn::UnOp::Neg(_), .. }) => {
+            let value = &default.value;
+            quote!(#value).to_string().replace(' ', "")
+        },
+        _ => panic!("#[property(default = ..)] expects a number, boolean or Cypher expression string"),
+    })
+}
+
+/// The value of a field's `#[serde(default)]` or `#[serde(default = "path")]`, if it has one
+fn serde_default(field: &syn::Field) -> Option<proc_macro2::TokenStream> {
+    let field_type = &field.ty;
+    let mut default = None;
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("default") {
+                if meta.input.peek(Token![=]) {
+                    let path: syn::Path = meta.value()?.parse::<syn::LitStr>()?.parse()?;
+                    default = Some(quote! { #path() });
+                } else {
+                    default = Some(quote! { <#field_type as ::std::default::Default>::default() });
+                }
+            } else if meta.input.peek(Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|nested| {
+                    if nested.input.peek(Token![=]) {
+                        nested.value()?.parse::<syn::Expr>()?;
+                    }
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        });
+    }
+    default
+}
@@ -18,18 +18,24 @@ pub fn derive_relationship(input: TokenStream) -> TokenStream {
     // Find the from/to fields and property fields
     let mut from_field = None;
     let mut to_field = None;
+    let mut from_type = None;
+    let mut to_type = None;
     let mut property_fields = Vec::new();
+    let mut validations = Vec::new();
     
     for field in fields.iter() {
+        validations.extend(crate::validate::field_checks(field));
         for attr in &field.attrs {
             let path = attr.path();
             let ident = path.get_ident().map(|i| i.to_string());
             match ident.as_deref() {
                 Some("from_node") => {
                     from_field = Some(field.ident.clone().unwrap());
+                    from_type = endpoint_type(attr);
                 },
                 Some("to_node") => {
                     to_field = Some(field.ident.clone().unwrap());
+                    to_type = endpoint_type(attr);
                 },
                 Some("property") => {
                     property_fields.push((field.ident.clone().unwrap(), &field.ty));
@@ -60,33 +66,101 @@ pub fn derive_relationship(input: TokenStream) -> TokenStream {
         format!("{} {}", name, type_str)
     }).collect::<Vec<_>>();
 
+    let from_label = endpoint_label(from_type.as_ref());
+    let to_label = endpoint_label(to_type.as_ref());
+    let from_key_methods = endpoint_key(&from_field, from_type.as_ref(), quote! { from }, quote! { source_key }, quote! { source_key_field });
+    let to_key_methods = endpoint_key(&to_field, to_type.as_ref(), quote! { to }, quote! { target_key }, quote! { target_key_field });
+    let from_name = from_field.to_string();
+    let to_name = to_field.to_string();
+    let validate_method = crate::validate::validate_method(&validations);
+
     let expanded = quote! {
         impl Relationship for #name {
             fn label() -> &'static str {
                 #label
             }
 
-            fn from(&self) -> String {
-                self.#from_field.to_string()
+            #from_key_methods
+
+            #to_key_methods
+
+            fn from_field() -> &'static str {
+                #from_name
             }
 
-            fn to(&self) -> String {
-                self.#to_field.to_string()
+            fn to_field() -> &'static str {
+                #to_name
             }
 
             fn from_node_label() -> &'static str {
-                "User"
+                #from_label
             }
 
             fn to_node_label() -> &'static str {
-                "User"
+                #to_label
             }
 
             fn property_definitions() -> Vec<String> {
                 vec![#(#property_defs.to_string()),*]
             }
+
+            #validate_method
+        }
+
+        impl ::okodata::FromValue for #name {
+            fn from_value(value: &::okodata::Value, row: &::okodata::Row) -> ::okodata::Result<Self> {
+                ::okodata::relationship_from_value::<Self>(value, row)
+            }
         }
     };
 
     TokenStream::from(expanded)
-} 
\ No newline at end of file
+}
+
+/// The node type named in `#[from_node(Type)]` or `#[to_node(Type)]`, if any
+fn endpoint_type(attr: &syn::Attribute) -> Option<syn::Path> {
+    match &attr.meta {
+        syn::Meta::Path(_) => None,
+        _ => Some(attr.parse_args().expect("Expected #[from_node(NodeType)] or #[to_node(NodeType)]")),
+    }
+}
+
+/// The label of an endpoint's node type; endpoints without one are `User` nodes
+fn endpoint_label(node_type: Option<&syn::Path>) -> proc_macro2::TokenStream {
+    match node_type {
+        Some(node_type) => quote! { <#node_type as ::okodata::Node>::label() },
+        None => quote! { "User" },
+    }
+}
+
+/// The methods reading an endpoint's key; typed endpoints convert the field into the node's key type
+fn endpoint_key(
+    field: &syn::Ident,
+    node_type: Option<&syn::Path>,
+    id: proc_macro2::TokenStream,
+    key: proc_macro2::TokenStream,
+    key_field: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match node_type {
+        Some(node_type) => quote! {
+            fn #id(&self) -> String {
+                let key: <#node_type as ::okodata::Node>::Key = self.#field.clone().into();
+                ::okodata::KeyType::to_key_string(&key)
+            }
+
+            fn #key(&self) -> ::okodata::Value {
+                let key: <#node_type as ::okodata::Node>::Key = self.#field.clone().into();
+                ::okodata::KeyType::to_value(&key)
+            }
+
+            fn #key_field() -> &'static str {
+                <#node_type as ::okodata::Node>::primary_key_field()
+            }
+        },
+        None => quote! {
+            fn #id(&self) -> String {
+                self.#field.to_string()
+            }
+        },
+    }
+}
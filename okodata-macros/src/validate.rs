@@ -0,0 +1,138 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{Expr, ExprLit, ExprUnary, Lit, Meta, MetaNameValue, Token, UnOp};
+
+/// Generates the checks for every `#[validate(...)]` attribute on a field
+///
+/// Supported rules are `range(min = .., max = ..)` on numeric fields,
+/// `length(min = .., max = ..)` on strings and vectors, `regex = ".."` on strings
+/// and `custom = "path::to::fn"`, where the function takes a reference to the
+/// field and returns `Result<(), String>`.
+pub fn field_checks(field: &syn::Field) -> Vec<TokenStream> {
+    let name = field.ident.clone().unwrap();
+    let name_str = name.to_string();
+    let mut checks = Vec::new();
+
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("validate")) {
+        let rules = attr
+            .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+            .expect("Expected #[validate(rule, ...)]");
+
+        for rule in rules {
+            let check = match &rule {
+                Meta::List(list) if list.path.is_ident("range") => {
+                    let (min, max) = bounds(list, |lit, negative| {
+                        let value = match lit {
+                            Lit::Int(i) => i.base10_parse::<f64>().ok(),
+                            Lit::Float(f) => f.base10_parse::<f64>().ok(),
+                            _ => None,
+                        }?;
+                        Some(if negative { -value } else { value })
+                    });
+                    let min = optional(min);
+                    let max = optional(max);
+                    quote! {
+                        ::okodata::validation::check_range(&mut errors, #name_str, self.#name as f64, #min, #max);
+                    }
+                },
+                Meta::List(list) if list.path.is_ident("length") => {
+                    let (min, max) = bounds(list, |lit, negative| match lit {
+                        Lit::Int(i) if !negative => i.base10_parse::<usize>().ok(),
+                        _ => None,
+                    });
+                    let min = optional(min);
+                    let max = optional(max);
+                    quote! {
+                        ::okodata::validation::check_length(&mut errors, #name_str, &self.#name, #min, #max);
+                    }
+                },
+                Meta::NameValue(nv) if nv.path.is_ident("regex") => {
+                    let pattern = string_value(&nv.value, "regex");
+                    if let Err(e) = regex::Regex::new(&pattern) {
+                        let message = format!("Invalid regex on {}: {}", name_str, e);
+                        checks.push(syn::Error::new_spanned(&nv.value, message).to_compile_error());
+                        continue;
+                    }
+                    quote! {
+                        ::okodata::validation::check_regex(&mut errors, #name_str, &self.#name, #pattern);
+                    }
+                },
+                Meta::NameValue(nv) if nv.path.is_ident("custom") => {
+                    let function: syn::Path = syn::parse_str(&string_value(&nv.value, "custom"))
+                        .expect("#[validate(custom = \"..\")] must name a function");
+                    quote! {
+                        ::okodata::validation::check_custom(&mut errors, #name_str, #function(&self.#name));
+                    }
+                },
+                _ => panic!("Unknown validation rule on {}; expected range, length, regex or custom", name_str),
+            };
+            checks.push(check);
+        }
+    }
+
+    checks
+}
+
+/// Generates a `validate` method running all checks, or nothing if there are none
+pub fn validate_method(checks: &[TokenStream]) -> TokenStream {
+    if checks.is_empty() {
+        return quote! {};
+    }
+    quote! {
+        fn validate(&self) -> ::std::result::Result<(), ::okodata::ValidationErrors> {
+            let mut errors = ::okodata::ValidationErrors::new();
+            #(#checks)*
+            errors.into_result()
+        }
+    }
+}
+
+/// Parses the `min` and `max` arguments of `range(...)` or `length(...)`
+///
+/// `parse` receives each literal and whether it was negated, as in `min = -10`.
+fn bounds<T>(list: &syn::MetaList, parse: impl Fn(&Lit, bool) -> Option<T>) -> (Option<T>, Option<T>) {
+    let args = list
+        .parse_args_with(Punctuated::<MetaNameValue, Token![,]>::parse_terminated)
+        .expect("Expected min = .. and/or max = ..");
+
+    let mut min = None;
+    let mut max = None;
+    for arg in args {
+        let value = match &arg.value {
+            Expr::Lit(ExprLit { lit, .. }) => parse(lit, false),
+            Expr::Unary(ExprUnary { op: UnOp::Neg(_), expr, .. }) => match expr.as_ref() {
+                Expr::Lit(ExprLit { lit, .. }) => parse(lit, true),
+                _ => None,
+            },
+            _ => None,
+        };
+        let value = value.expect("Validation bounds must be number literals");
+        if arg.path.is_ident("min") {
+            min = Some(value);
+        } else if arg.path.is_ident("max") {
+            max = Some(value);
+        } else {
+            panic!("Unexpected validation bound, expected min or max");
+        }
+    }
+
+    if min.is_none() && max.is_none() {
+        panic!("Validation rule needs at least one of min and max");
+    }
+    (min, max)
+}
+
+fn optional<T: quote::ToTokens>(value: Option<T>) -> TokenStream {
+    match value {
+        Some(value) => quote! { ::std::option::Option::Some(#value) },
+        None => quote! { ::std::option::Option::None },
+    }
+}
+
+fn string_value(expr: &Expr, rule: &str) -> String {
+    match expr {
+        Expr::Lit(ExprLit { lit: Lit::Str(s), .. }) => s.value(),
+        _ => panic!("#[validate({} = ..)] expects a string", rule),
+    }
+}
@@ -0,0 +1,541 @@
+//! In-memory graph algorithms over a projection of node and relationship tables
+//!
+//! A [`Projection`] loads only node keys and edges, so the algorithms run on the
+//! client without deserializing full nodes. Results are keyed by [`NodeKey`] and can
+//! be stored on the nodes again with [`write_back`].
+
+use crate::{Result, Error, Node, Relationship, KuzuGraph, Direction, KeyType};
+use crate::row::value_to_json;
+use kuzu::Value;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Identifies a node in a projection by its label and primary key
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeKey {
+    /// Label of the node's table
+    pub label: String,
+    /// Primary key of the node, as a string
+    pub id: String,
+}
+
+impl NodeKey {
+    /// Creates a key for the node with primary key `id` in table `label`
+    pub fn new<L: Into<String>, I: Into<String>>(label: L, id: I) -> Self {
+        Self { label: label.into(), id: id.into() }
+    }
+}
+
+/// What a BFS or DFS visitor wants to happen after visiting a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    /// Keep searching, including the node's neighbors
+    Continue,
+    /// Keep searching, but do not expand the node's neighbors
+    Prune,
+    /// End the search
+    Stop,
+}
+
+/// Chooses the node and relationship tables to load into a [`Projection`]
+#[derive(Debug, Default, Clone)]
+pub struct ProjectionBuilder {
+    /// Node labels with their primary key fields
+    nodes: Vec<(&'static str, &'static str)>,
+    /// Relationship labels with their source and target node labels
+    relationships: Vec<(&'static str, &'static str, &'static str)>,
+}
+
+impl ProjectionBuilder {
+    /// Creates an empty projection builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Includes every node of type `N`
+    #[must_use]
+    pub fn node<N: Node>(mut self) -> Self {
+        self.nodes.push((N::label(), N::primary_key_field()));
+        self
+    }
+
+    /// Includes every relationship of type `R`
+    ///
+    /// The endpoint tables are the node types named in `#[from_node(..)]` and
+    /// `#[to_node(..)]`; both must be included as well.
+    #[must_use]
+    pub fn relationship<R: Relationship>(mut self) -> Self {
+        self.relationships.push((R::label(), R::from_node_label(), R::to_node_label()));
+        self
+    }
+
+    /// Loads the chosen tables from the graph
+    ///
+    /// # Returns
+    /// * `Result<Projection>` - The in-memory projection or an error
+    pub fn load(&self, graph: &KuzuGraph) -> Result<Projection> {
+        let pk_fields: HashMap<&str, &str> = self.nodes.iter().copied().collect();
+
+        let mut nodes = Vec::new();
+        for (label, pk) in &self.nodes {
+            let result = graph.query(&format!("MATCH (n:{}) RETURN n.{}", label, pk))?;
+            for row in result {
+                nodes.push(NodeKey::new(*label, key_string(&row[0])));
+            }
+        }
+
+        let mut edges = Vec::new();
+        for (label, from, to) in &self.relationships {
+            let endpoint_pk = |endpoint: &str| pk_fields.get(endpoint).copied().ok_or_else(|| Error::Schema(
+                format!("Relationship {} needs node table {} in the projection", label, endpoint)
+            ));
+            let query = format!(
+                "MATCH (a:{})-[:{}]->(b:{}) RETURN a.{}, b.{}",
+                from, label, to, endpoint_pk(from)?, endpoint_pk(to)?
+            );
+            for row in graph.query(&query)? {
+                edges.push((
+                    NodeKey::new(*from, key_string(&row[0])),
+                    NodeKey::new(*to, key_string(&row[1])),
+                ));
+            }
+        }
+
+        Projection::new(nodes, edges)
+    }
+}
+
+/// An in-memory directed graph of node keys and edges
+#[derive(Debug, Clone)]
+pub struct Projection {
+    keys: Vec<NodeKey>,
+    index: HashMap<NodeKey, usize>,
+    outgoing: Vec<Vec<usize>>,
+    incoming: Vec<Vec<usize>>,
+}
+
+impl Projection {
+    /// Creates a projection from nodes and the directed edges between them
+    ///
+    /// # Returns
+    /// * `Result<Self>` - The projection, or an error if an edge refers to an unknown node
+    pub fn new(nodes: Vec<NodeKey>, edges: Vec<(NodeKey, NodeKey)>) -> Result<Self> {
+        let mut keys = Vec::with_capacity(nodes.len());
+        let mut index = HashMap::with_capacity(nodes.len());
+        for key in nodes {
+            if !index.contains_key(&key) {
+                index.insert(key.clone(), keys.len());
+                keys.push(key);
+            }
+        }
+
+        let mut outgoing = vec![Vec::new(); keys.len()];
+        let mut incoming = vec![Vec::new(); keys.len()];
+        for (from, to) in edges {
+            let lookup = |key: &NodeKey| index.get(key).copied().ok_or_else(|| {
+                Error::NodeNotFound(format!("{}:{}", key.label, key.id))
+            });
+            let (from, to) = (lookup(&from)?, lookup(&to)?);
+            outgoing[from].push(to);
+            incoming[to].push(from);
+        }
+
+        Ok(Self { keys, index, outgoing, incoming })
+    }
+
+    /// Number of nodes in the projection
+    pub fn node_count(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Number of edges in the projection
+    pub fn edge_count(&self) -> usize {
+        self.outgoing.iter().map(Vec::len).sum()
+    }
+
+    /// Keys of all nodes in the projection
+    pub fn nodes(&self) -> &[NodeKey] {
+        &self.keys
+    }
+
+    fn neighbors(&self, node: usize, direction: Direction) -> Box<dyn Iterator<Item = usize> + '_> {
+        match direction {
+            Direction::Outgoing => Box::new(self.outgoing[node].iter().copied()),
+            Direction::Incoming => Box::new(self.incoming[node].iter().copied()),
+            Direction::Both => Box::new(
+                self.outgoing[node].iter().chain(self.incoming[node].iter()).copied()
+            ),
+        }
+    }
+
+    fn keyed<T>(&self, values: Vec<T>) -> HashMap<NodeKey, T> {
+        self.keys.iter().cloned().zip(values).collect()
+    }
+
+    /// Computes the PageRank of every node
+    ///
+    /// # Arguments
+    /// * `damping` - Probability of following an edge rather than jumping, usually 0.85
+    /// * `iterations` - Number of power iterations to run
+    ///
+    /// # Returns
+    /// * The rank of every node; ranks sum to 1
+    pub fn page_rank(&self, damping: f64, iterations: usize) -> HashMap<NodeKey, f64> {
+        let n = self.keys.len();
+        if n == 0 {
+            return HashMap::new();
+        }
+
+        let mut ranks = vec![1.0 / n as f64; n];
+        for _ in 0..iterations {
+            // Rank held by nodes without outgoing edges is spread evenly
+            let dangling: f64 = (0..n)
+                .filter(|&i| self.outgoing[i].is_empty())
+                .map(|i| ranks[i])
+                .sum();
+            let base = (1.0 - damping) / n as f64 + damping * dangling / n as f64;
+
+            let mut next = vec![base; n];
+            for (i, targets) in self.outgoing.iter().enumerate() {
+                let share = damping * ranks[i] / targets.len().max(1) as f64;
+                for &j in targets {
+                    next[j] += share;
+                }
+            }
+            ranks = next;
+        }
+
+        self.keyed(ranks)
+    }
+
+    /// Finds the weakly connected components, ignoring edge direction
+    ///
+    /// # Returns
+    /// * The component number of every node; nodes in the same component share a number
+    pub fn weakly_connected_components(&self) -> HashMap<NodeKey, usize> {
+        let n = self.keys.len();
+        let mut parent: Vec<usize> = (0..n).collect();
+
+        fn find(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+
+        for (from, targets) in self.outgoing.iter().enumerate() {
+            for &to in targets {
+                let (a, b) = (find(&mut parent, from), find(&mut parent, to));
+                if a != b {
+                    parent[a.max(b)] = a.min(b);
+                }
+            }
+        }
+
+        let mut numbers = HashMap::new();
+        let components = (0..n)
+            .map(|i| {
+                let root = find(&mut parent, i);
+                let next = numbers.len();
+                *numbers.entry(root).or_insert(next)
+            })
+            .collect();
+        self.keyed(components)
+    }
+
+    /// Finds the strongly connected components using Tarjan's algorithm
+    ///
+    /// # Returns
+    /// * The component number of every node; nodes in the same component share a number
+    pub fn strongly_connected_components(&self) -> HashMap<NodeKey, usize> {
+        let n = self.keys.len();
+        let mut index = vec![usize::MAX; n];
+        let mut low = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = Vec::new();
+        let mut components = vec![0; n];
+        let mut next_index = 0;
+        let mut next_component = 0;
+
+        for root in 0..n {
+            if index[root] != usize::MAX {
+                continue;
+            }
+
+            // Each frame is a node and the position of the next edge to explore
+            let mut frames = vec![(root, 0)];
+            index[root] = next_index;
+            low[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some(&mut (node, ref mut edge)) = frames.last_mut() {
+                if let Some(&next) = self.outgoing[node].get(*edge) {
+                    *edge += 1;
+                    if index[next] == usize::MAX {
+                        index[next] = next_index;
+                        low[next] = next_index;
+                        next_index += 1;
+                        stack.push(next);
+                        on_stack[next] = true;
+                        frames.push((next, 0));
+                    } else if on_stack[next] {
+                        low[node] = low[node].min(index[next]);
+                    }
+                    continue;
+                }
+
+                frames.pop();
+                if let Some(&(parent, _)) = frames.last() {
+                    low[parent] = low[parent].min(low[node]);
+                }
+                if low[node] == index[node] {
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        components[member] = next_component;
+                        if member == node {
+                            break;
+                        }
+                    }
+                    next_component += 1;
+                }
+            }
+        }
+
+        self.keyed(components)
+    }
+
+    /// Computes the degree centrality of every node
+    ///
+    /// # Arguments
+    /// * `direction` - Which edges count towards the degree
+    ///
+    /// # Returns
+    /// * The degree of every node divided by the number of other nodes
+    pub fn degree_centrality(&self, direction: Direction) -> HashMap<NodeKey, f64> {
+        let others = self.keys.len().saturating_sub(1).max(1) as f64;
+        let degrees = (0..self.keys.len())
+            .map(|i| self.neighbors(i, direction).count() as f64 / others)
+            .collect();
+        self.keyed(degrees)
+    }
+
+    /// Counts the triangles each node is part of, ignoring edge direction
+    ///
+    /// Parallel edges and self-loops are ignored; the total number of triangles
+    /// is the sum of the counts divided by three.
+    ///
+    /// # Returns
+    /// * The number of triangles through every node
+    pub fn triangle_count(&self) -> HashMap<NodeKey, usize> {
+        let n = self.keys.len();
+        let adjacent: Vec<HashSet<usize>> = (0..n)
+            .map(|i| self.neighbors(i, Direction::Both).filter(|&j| j != i).collect())
+            .collect();
+
+        let mut counts = vec![0; n];
+        for u in 0..n {
+            for &v in adjacent[u].iter().filter(|&&v| v > u) {
+                for &w in adjacent[v].iter().filter(|&&w| w > v) {
+                    if adjacent[u].contains(&w) {
+                        counts[u] += 1;
+                        counts[v] += 1;
+                        counts[w] += 1;
+                    }
+                }
+            }
+        }
+
+        self.keyed(counts)
+    }
+
+    /// Visits nodes in breadth-first order from `start`
+    ///
+    /// # Arguments
+    /// * `start` - Node to start from
+    /// * `direction` - Which edges to follow
+    /// * `visitor` - Called with each node and its depth, in visiting order
+    pub fn bfs<F>(&self, start: &NodeKey, direction: Direction, mut visitor: F) -> Result<()>
+    where
+        F: FnMut(&NodeKey, usize) -> Control,
+    {
+        let start = self.position(start)?;
+        let mut seen = vec![false; self.keys.len()];
+        let mut queue = VecDeque::from([(start, 0)]);
+        seen[start] = true;
+
+        while let Some((node, depth)) = queue.pop_front() {
+            match visitor(&self.keys[node], depth) {
+                Control::Stop => break,
+                Control::Prune => continue,
+                Control::Continue => {},
+            }
+            for next in self.neighbors(node, direction) {
+                if !seen[next] {
+                    seen[next] = true;
+                    queue.push_back((next, depth + 1));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Visits nodes in depth-first (pre-)order from `start`
+    ///
+    /// # Arguments
+    /// * `start` - Node to start from
+    /// * `direction` - Which edges to follow
+    /// * `visitor` - Called with each node and its depth, in visiting order
+    pub fn dfs<F>(&self, start: &NodeKey, direction: Direction, mut visitor: F) -> Result<()>
+    where
+        F: FnMut(&NodeKey, usize) -> Control,
+    {
+        let start = self.position(start)?;
+        let mut seen = vec![false; self.keys.len()];
+        let mut stack = vec![(start, 0)];
+
+        while let Some((node, depth)) = stack.pop() {
+            if seen[node] {
+                continue;
+            }
+            seen[node] = true;
+            match visitor(&self.keys[node], depth) {
+                Control::Stop => break,
+                Control::Prune => continue,
+                Control::Continue => {},
+            }
+            // Push in reverse so neighbors are visited in edge order
+            let neighbors: Vec<usize> = self.neighbors(node, direction).collect();
+            for &next in neighbors.iter().rev() {
+                if !seen[next] {
+                    stack.push((next, depth + 1));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn position(&self, key: &NodeKey) -> Result<usize> {
+        self.index
+            .get(key)
+            .copied()
+            .ok_or_else(|| Error::NodeNotFound(format!("{}:{}", key.label, key.id)))
+    }
+}
+
+/// Stores algorithm results on the nodes of type `N` they belong to
+///
+/// Each node is fetched, modified by `apply` and saved with [`KuzuGraph::update_node`].
+/// Results for nodes of other labels are skipped.
+///
+/// # Arguments
+/// * `graph` - The graph the projection was loaded from
+/// * `results` - Per-node results, e.g. from [`Projection::page_rank`]
+/// * `apply` - Copies a result into the node
+///
+/// # Returns
+/// * `Result<usize>` - The number of nodes updated
+pub fn write_back<N, T, F>(graph: &KuzuGraph, results: &HashMap<NodeKey, T>, mut apply: F) -> Result<usize>
+where
+    N: Node,
+    F: FnMut(&mut N, &T),
+{
+    let mut updated = 0;
+    for (key, value) in results {
+        if key.label != N::label() {
+            continue;
+        }
+        let mut node = graph
+            .find_node::<N>(N::Key::from_key_string(&key.id)?)?
+            .ok_or_else(|| Error::NodeNotFound(key.id.clone()))?;
+        apply(&mut node, value);
+        graph.update_node(&node)?;
+        updated += 1;
+    }
+    Ok(updated)
+}
+
+/// Renders a primary key value as a string
+fn key_string(value: &Value) -> String {
+    match value_to_json(value) {
+        Some(serde_json::Value::String(s)) => s,
+        Some(other) => other.to_string(),
+        None => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(id: &str) -> NodeKey {
+        NodeKey::new("User", id)
+    }
+
+    /// a -> b -> c -> a forms a cycle, c -> d hangs off it, e is isolated
+    fn projection() -> Projection {
+        let nodes = ["a", "b", "c", "d", "e"].iter().map(|id| key(id)).collect();
+        let edges = [("a", "b"), ("b", "c"), ("c", "a"), ("c", "d")]
+            .iter()
+            .map(|(from, to)| (key(from), key(to)))
+            .collect();
+        Projection::new(nodes, edges).unwrap()
+    }
+
+    #[test]
+    fn test_page_rank() {
+        let ranks = projection().page_rank(0.85, 50);
+
+        let total: f64 = ranks.values().sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert!(ranks[&key("a")] > ranks[&key("e")]);
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let p = projection();
+
+        let weak = p.weakly_connected_components();
+        assert_eq!(weak[&key("a")], weak[&key("d")]);
+        assert_ne!(weak[&key("a")], weak[&key("e")]);
+
+        let strong = p.strongly_connected_components();
+        assert_eq!(strong[&key("a")], strong[&key("c")]);
+        assert_ne!(strong[&key("a")], strong[&key("d")]);
+    }
+
+    #[test]
+    fn test_degree_and_triangles() {
+        let p = projection();
+
+        let degree = p.degree_centrality(Direction::Outgoing);
+        assert_eq!(degree[&key("c")], 0.5);
+
+        let triangles = p.triangle_count();
+        assert_eq!(triangles[&key("a")], 1);
+        assert_eq!(triangles[&key("d")], 0);
+    }
+
+    #[test]
+    fn test_bfs_and_dfs() {
+        let p = projection();
+
+        let mut visited = Vec::new();
+        p.bfs(&key("a"), Direction::Outgoing, |node, depth| {
+            visited.push((node.id.clone(), depth));
+            Control::Continue
+        }).unwrap();
+        assert_eq!(visited, vec![
+            ("a".to_string(), 0), ("b".to_string(), 1), ("c".to_string(), 2), ("d".to_string(), 3),
+        ]);
+
+        let mut visited = Vec::new();
+        p.dfs(&key("a"), Direction::Both, |node, _| {
+            visited.push(node.id.clone());
+            if node.id == "c" { Control::Prune } else { Control::Continue }
+        }).unwrap();
+        assert_eq!(visited, vec!["a", "b", "c"]);
+    }
+}
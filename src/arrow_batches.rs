@@ -0,0 +1,151 @@
+use crate::{Result, Error, Node, KuzuGraph};
+use crate::row::node_from_json;
+use arrow::array::{Array, AsArray};
+use arrow::datatypes::{
+    DataType, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type,
+    UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+};
+use arrow::record_batch::RecordBatch;
+use kuzu::{ArrowIterator, Connection, QueryResult};
+use serde_json::json;
+use std::mem::ManuallyDrop;
+
+/// Iterator over the results of a query as Arrow [`RecordBatch`]es
+///
+/// Holds the connection the query ran on, so the result stays valid for
+/// as long as the iterator is alive. Batches are converted by kuzu as they are
+/// read; kuzu panics if converting one fails, so the items are not `Result`s.
+pub struct ArrowBatches<'g> {
+    // The batch iterator borrows the result, so both are dropped by hand,
+    // iterator first, before the connection the result needs
+    batches: ManuallyDrop<ArrowIterator<'static>>,
+    result: *mut QueryResult,
+    _connection: Connection<'g>,
+}
+
+impl Iterator for ArrowBatches<'_> {
+    type Item = RecordBatch;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.batches.next()
+    }
+}
+
+impl Drop for ArrowBatches<'_> {
+    fn drop(&mut self) {
+        // SAFETY: `result` comes from `Box::into_raw` in `query_arrow` and is only
+        // reached through `batches`, which is dropped here first and never used again
+        unsafe {
+            ManuallyDrop::drop(&mut self.batches);
+            drop(Box::from_raw(self.result));
+        }
+    }
+}
+
+impl KuzuGraph {
+    /// Execute a raw Cypher query and stream the results as Arrow record batches
+    ///
+    /// # Arguments
+    /// * `query` - The Cypher query to run
+    /// * `batch_size` - Maximum number of rows per record batch
+    ///
+    /// # Returns
+    /// * `Result<ArrowBatches>` - An iterator over the record batches or an error
+    pub fn query_arrow(&self, query: &str, batch_size: usize) -> Result<ArrowBatches<'_>> {
+        if batch_size == 0 {
+            return Err(Error::Query("Arrow batch size must be greater than zero".into()));
+        }
+        let connection = self.get_connection()?;
+        let result = Box::into_raw(Box::new(connection.query(query)?));
+
+        // SAFETY: the result is leaked into a raw pointer, so nothing else owns or
+        // moves it while the iterator borrows it; `ArrowBatches` frees it on drop
+        let batches = match unsafe { &mut *result }.iter_arrow(batch_size) {
+            Ok(batches) => batches,
+            Err(e) => {
+                // SAFETY: the failed call left no borrow of the result behind
+                drop(unsafe { Box::from_raw(result) });
+                return Err(e.into());
+            },
+        };
+
+        Ok(ArrowBatches {
+            batches: ManuallyDrop::new(batches),
+            result,
+            _connection: connection,
+        })
+    }
+}
+
+/// Deserializes every row of a record batch into a node
+///
+/// If the batch has a single struct column (as produced by `RETURN n`), each node is
+/// read from that column's fields. Otherwise the columns themselves are used as the
+/// node properties, as produced by `RETURN n.id AS id, n.name AS name`.
+pub fn batch_to_nodes<N: Node>(batch: &RecordBatch) -> Result<Vec<N>> {
+    let schema = batch.schema();
+    let single_node_column = batch.num_columns() == 1
+        && matches!(schema.field(0).data_type(), DataType::Struct(_));
+
+    let mut nodes = Vec::with_capacity(batch.num_rows());
+    for row in 0..batch.num_rows() {
+        let props = if single_node_column {
+            array_value(batch.column(0).as_ref(), row)?
+        } else {
+            let mut props = serde_json::Map::new();
+            for (field, column) in schema.fields().iter().zip(batch.columns()) {
+                props.insert(field.name().clone(), array_value(column.as_ref(), row)?);
+            }
+            serde_json::Value::Object(props)
+        };
+        nodes.push(node_from_json(props)?);
+    }
+    Ok(nodes)
+}
+
+/// Converts a single cell of an Arrow array to a JSON value
+///
+/// # Returns
+/// * `Result<serde_json::Value>` - The value, or an error for Arrow types with no JSON mapping
+fn array_value(array: &dyn Array, row: usize) -> Result<serde_json::Value> {
+    if array.is_null(row) {
+        return Ok(serde_json::Value::Null);
+    }
+
+    Ok(match array.data_type() {
+        DataType::Boolean => json!(array.as_boolean().value(row)),
+        DataType::Int8 => json!(array.as_primitive::<Int8Type>().value(row)),
+        DataType::Int16 => json!(array.as_primitive::<Int16Type>().value(row)),
+        DataType::Int32 => json!(array.as_primitive::<Int32Type>().value(row)),
+        DataType::Int64 => json!(array.as_primitive::<Int64Type>().value(row)),
+        DataType::UInt8 => json!(array.as_primitive::<UInt8Type>().value(row)),
+        DataType::UInt16 => json!(array.as_primitive::<UInt16Type>().value(row)),
+        DataType::UInt32 => json!(array.as_primitive::<UInt32Type>().value(row)),
+        DataType::UInt64 => json!(array.as_primitive::<UInt64Type>().value(row)),
+        DataType::Float32 => json!(array.as_primitive::<Float32Type>().value(row)),
+        DataType::Float64 => json!(array.as_primitive::<Float64Type>().value(row)),
+        DataType::Utf8 => json!(array.as_string::<i32>().value(row)),
+        DataType::LargeUtf8 => json!(array.as_string::<i64>().value(row)),
+        DataType::Struct(fields) => {
+            let array = array.as_struct();
+            let mut obj = serde_json::Map::new();
+            for (field, column) in fields.iter().zip(array.columns()) {
+                obj.insert(field.name().clone(), array_value(column.as_ref(), row)?);
+            }
+            serde_json::Value::Object(obj)
+        },
+        DataType::List(_) => list_value(array.as_list::<i32>().value(row).as_ref())?,
+        DataType::LargeList(_) => list_value(array.as_list::<i64>().value(row).as_ref())?,
+        DataType::FixedSizeList(_, _) => list_value(array.as_fixed_size_list().value(row).as_ref())?,
+        other => return Err(Error::InvalidPropertyType(
+            format!("Unsupported Arrow type {} in record batch", other)
+        )),
+    })
+}
+
+fn list_value(values: &dyn Array) -> Result<serde_json::Value> {
+    (0..values.len())
+        .map(|i| array_value(values, i))
+        .collect::<Result<Vec<_>>>()
+        .map(serde_json::Value::Array)
+}
@@ -0,0 +1,145 @@
+use crate::{Result, Error, KuzuGraph};
+use serde::{Serialize, Deserialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the manifest file written next to the exported database files
+pub const MANIFEST_FILE: &str = "okodata_manifest.json";
+
+/// Version of the tables and columns OkoData itself defines in a database
+///
+/// Bump it when a release changes that layout, so that older snapshots are refused.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Describes a snapshot produced by [`KuzuGraph::backup_to`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    /// [`SCHEMA_VERSION`] of the OkoData release that wrote the snapshot
+    pub schema_version: u32,
+    /// Storage version of the KuzuDB library that exported the snapshot
+    pub storage_version: u64,
+    /// Hex-encoded SHA-256 over the exported files
+    pub checksum: String,
+}
+
+impl BackupManifest {
+    /// Reads the manifest stored in a backup directory
+    pub fn read<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let contents = fs::read_to_string(dir.as_ref().join(MANIFEST_FILE))?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+}
+
+impl KuzuGraph {
+    /// Exports the whole database into `dir` and records a manifest alongside it
+    ///
+    /// # Arguments
+    /// * `dir` - Directory to export into; KuzuDB requires that it does not exist yet
+    ///
+    /// # Returns
+    /// * `Result<BackupManifest>` - The manifest written into the backup directory
+    pub fn backup_to<P: AsRef<Path>>(&self, dir: P) -> Result<BackupManifest> {
+        let dir = dir.as_ref();
+        let query = format!("EXPORT DATABASE '{}'", escape_path(dir));
+        self.query(&query)?;
+
+        let manifest = BackupManifest {
+            schema_version: SCHEMA_VERSION,
+            storage_version: kuzu::get_storage_version(),
+            checksum: checksum_dir(dir)?,
+        };
+        fs::write(dir.join(MANIFEST_FILE), serde_json::to_string_pretty(&manifest)?)?;
+        Ok(manifest)
+    }
+
+    /// Creates a new database at `path` from a snapshot produced by [`KuzuGraph::backup_to`]
+    ///
+    /// The snapshot is refused if its files do not match the recorded checksum, or if it
+    /// was written by a different OkoData schema version or KuzuDB storage version.
+    ///
+    /// # Arguments
+    /// * `dir` - Backup directory containing the exported files and the manifest
+    /// * `path` - Path of the new KuzuDB database directory
+    ///
+    /// # Returns
+    /// * `Result<Self>` - A KuzuGraph over the restored database or an error
+    pub fn restore_from<D: AsRef<Path>, P: AsRef<Path>>(dir: D, path: P) -> Result<Self> {
+        let dir = dir.as_ref();
+        let manifest = BackupManifest::read(dir)?;
+
+        if manifest.schema_version != SCHEMA_VERSION {
+            return Err(Error::Backup(format!(
+                "snapshot schema version {} does not match {}",
+                manifest.schema_version,
+                SCHEMA_VERSION
+            )));
+        }
+        if manifest.storage_version != kuzu::get_storage_version() {
+            return Err(Error::Backup(format!(
+                "snapshot storage version {} does not match {}",
+                manifest.storage_version,
+                kuzu::get_storage_version()
+            )));
+        }
+        let checksum = checksum_dir(dir)?;
+        if manifest.checksum != checksum {
+            return Err(Error::Backup(format!(
+                "checksum mismatch: manifest has {}, files hash to {}",
+                manifest.checksum, checksum
+            )));
+        }
+
+        let graph = KuzuGraph::new(path)?;
+        let query = format!("IMPORT DATABASE '{}'", escape_path(dir));
+        graph.query(&query)?;
+        Ok(graph)
+    }
+}
+
+/// Escapes a path for use inside a single-quoted Cypher string
+fn escape_path(path: &Path) -> String {
+    path.display().to_string().replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+/// Hashes every file under `dir` except the manifest, in a stable order
+fn checksum_dir(dir: &Path) -> Result<String> {
+    let mut files = Vec::new();
+    collect_files(dir, &mut files)?;
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for file in files {
+        let relative = file.strip_prefix(dir).unwrap_or(&file);
+        if relative == Path::new(MANIFEST_FILE) {
+            continue;
+        }
+        hasher.update(relative.to_string_lossy().as_bytes());
+        hasher.update(fs::read(&file)?);
+    }
+
+    Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_path() {
+        assert_eq!(escape_path(Path::new("backups/it's")), "backups/it\\'s");
+        assert_eq!(escape_path(Path::new("C:\\backups\\okodata")), "C:\\\\backups\\\\okodata");
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{Result, Node, Relationship, KuzuGraph, FromRow, FromValue, PrimaryKeys, Row, Direction, QueryBuilder};
+use crate::query::Fragment;
+use kuzu::Value;
+use std::marker::PhantomData;
+
+/// A node fetched together with eagerly loaded related data
+#[derive(Debug, Clone, PartialEq)]
+pub struct Loaded<N, T> {
+    /// The root node
+    pub node: N,
+    /// The data loaded alongside the node
+    pub related: T,
+}
+
+/// A root node with each relationship of type `R` and the `M` node at its other end
+pub type LoadedWith<N, R, M> = Loaded<N, Vec<(R, M)>>;
+
+/// The part shared by queries that match the root nodes of a [`QueryBuilder`], then
+/// `OPTIONAL MATCH` their relationships `r` of type `R` to nodes `m` of type `M`
+#[derive(Debug)]
+pub(crate) struct RelatedQuery<N, R, M> {
+    /// The query selecting the root nodes
+    pub(crate) query: QueryBuilder<N>,
+    /// Direction of the relationships, seen from the root nodes
+    pub(crate) direction: Direction,
+    /// Phantom data to hold the relationship and related node types
+    types: PhantomData<(R, M)>,
+}
+
+impl<N: Node, R: Relationship, M: Node> RelatedQuery<N, R, M> {
+    pub(crate) fn new(query: QueryBuilder<N>) -> Self {
+        Self {
+            query,
+            direction: Direction::Outgoing,
+            types: PhantomData,
+        }
+    }
+
+    /// Renders the root query as a `WITH n` stage followed by the OPTIONAL MATCH
+    pub(crate) fn render_match(&self) -> Fragment {
+        let mut query = self.query.render_stage("WITH", "n".into(), &self.query.order_clause());
+        query.push_str(&format!(
+            " OPTIONAL MATCH (n){}(m:{})",
+            self.direction.pattern(&format!("r:{}", R::label())),
+            M::label(),
+        ));
+        query
+    }
+}
+
+/// A query that loads each matching node together with its related nodes,
+/// created by [`QueryBuilder::with`]
+///
+/// The related nodes and relationships are fetched in the same round-trip using
+/// `OPTIONAL MATCH` and `COLLECT`, so nodes without relationships are still returned.
+#[derive(Debug)]
+pub struct EagerQuery<N, R, M> {
+    related: RelatedQuery<N, R, M>,
+}
+
+impl<N: Node, R: Relationship, M: Node> EagerQuery<N, R, M> {
+    pub(crate) fn new(query: QueryBuilder<N>) -> Self {
+        Self { related: RelatedQuery::new(query) }
+    }
+
+    /// Sets the direction of the relationships to load
+    ///
+    /// # Arguments
+    /// * `direction` - Direction seen from the root nodes, outgoing by default
+    ///
+    /// # Returns
+    /// * Self for method chaining
+    #[must_use]
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.related.direction = direction;
+        self
+    }
+
+    /// Builds the Cypher query string
+    ///
+    /// # Returns
+    /// * A string containing the complete Cypher query
+    pub fn build(&self) -> String {
+        self.render().text()
+    }
+
+    fn render(&self) -> Fragment {
+        let mut query = self.related.render_match();
+        query.push_str(&format!(" RETURN n, COLLECT({{r: r, m: m}}){}", self.related.query.order_clause()));
+        query
+    }
+
+    /// Runs the query and returns each root node with its relationships and related nodes
+    ///
+    /// # Arguments
+    /// * `graph` - The graph to run the query against
+    ///
+    /// # Returns
+    /// * `Result<Vec<LoadedWith<N, R, M>>>` - The loaded nodes or an error
+    pub fn fetch(&self, graph: &KuzuGraph) -> Result<Vec<LoadedWith<N, R, M>>>
+    where
+        N: FromValue,
+        R: FromValue,
+        M: FromValue,
+    {
+        let (query, params) = self.render().finish()?;
+        graph.query_as(&query, params.as_vec())
+    }
+}
+
+/// Reads a row of the form `RETURN n, COLLECT({r: r, m: m})`
+impl<N: FromValue, R: FromValue, M: FromValue> FromRow for LoadedWith<N, R, M> {
+    fn from_row(row: &Row) -> Result<Self> {
+        let node = row.get_index::<N>(0)?;
+        let pairs = row.get_index::<Vec<Value>>(1)?;
+
+        let mut related = Vec::with_capacity(pairs.len());
+        for pair in &pairs {
+            let Value::Struct(fields) = pair else { continue };
+            let field = |name: &str| fields.iter().find(|(key, _)| key == name).map(|(_, value)| value);
+
+            // A node without relationships collects a single pair of nulls
+            if let (Some(rel @ Value::Rel(_)), Some(node @ Value::Node(_))) = (field("r"), field("m")) {
+                related.push((R::from_value(rel, row)?, M::from_value(node, row)?));
+            }
+        }
+
+        Ok(Loaded { node, related })
+    }
+
+    fn register_keys(keys: &mut PrimaryKeys) {
+        N::register_keys(keys);
+        R::register_keys(keys);
+        M::register_keys(keys);
+    }
+}
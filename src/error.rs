@@ -1,3 +1,4 @@
+use crate::ValidationErrors;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -19,4 +20,24 @@ pub enum Error {
 
     #[error("Query error: {0}")]
     Query(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Backup error: {0}")]
+    Backup(String),
+
+    #[error("Validation error: {0}")]
+    Validation(#[from] ValidationErrors),
+
+    #[error("Constraint violation: {label}.{field} already has value {value}")]
+    ConstraintViolation {
+        label: String,
+        field: String,
+        value: String,
+    },
+
+    #[cfg(feature = "arrow")]
+    #[error("Arrow error: {0}")]
+    Arrow(#[from] arrow::error::ArrowError),
 } 
\ No newline at end of file
@@ -1,14 +1,19 @@
-use crate::{Result, Node, Relationship, Error};
+use crate::{Result, Node, Relationship, Error, KeyType};
+use crate::row::{node_to_json, node_from_json, json_to_value};
+use crate::key::key_column;
+use crate::schema::property_column;
+use crate::vector::{vector_columns, vector_dim, vector_value};
 use kuzu::{Database, SystemConfig, Connection, Value};
 use std::path::Path;
-use std::sync::Arc;
-use serde_json::json;
+use std::sync::{Arc, Mutex};
 
 /// Main interface for interacting with a KuzuDB graph database
 #[derive(Debug)]
 pub struct KuzuGraph {
     /// The underlying KuzuDB database instance
     db: Arc<Database>,
+    /// Whether the unique-constraint registry table exists, once it has been looked up
+    pub(crate) index_table: Mutex<Option<bool>>,
 }
 
 /// Represents an active database transaction
@@ -69,7 +74,7 @@ impl KuzuGraph {
     /// * `Result<Self>` - A new KuzuGraph instance or an error
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
         let db = Arc::new(Database::new(path, SystemConfig::default())?);
-        Ok(Self { db })
+        Ok(Self { db, index_table: Mutex::new(None) })
     }
 
     /// Gets a new connection to the database
@@ -80,16 +85,27 @@ impl KuzuGraph {
         Ok(Connection::new(&*self.db)?)
     }
 
-    /// Create a node in the graph
-    pub fn create_node<N: Node>(&self, node: &N) -> Result<()> {
+    /// Create a node in the graph, replacing any node with the same primary key
+    ///
+    /// Nodes with a generated key (`#[id(auto = "..")]`) are always inserted as new
+    /// nodes; the key field of `node` is ignored and the database assigns one.
+    ///
+    /// # Returns
+    /// * `Result<N::Key>` - The primary key of the created node
+    pub fn create_node<N: Node>(&self, node: &N) -> Result<N::Key> {
+        node.validate()?;
         let label = N::label();
+        let pk_field = N::primary_key_field();
         let props = node.properties();
         let conn = self.get_connection()?;
         
         // First create the node table if it doesn't exist
-        let mut columns = Vec::new();
+        let mut columns = vec![key_column::<N>()];
         if let serde_json::Value::Object(obj) = &props {
             for (key, value) in obj {
+                if key == pk_field {
+                    continue;
+                }
                 let col_type = match value {
                     serde_json::Value::String(_) => "STRING",
                     serde_json::Value::Number(n) => {
@@ -102,28 +118,41 @@ impl KuzuGraph {
                     serde_json::Value::Bool(_) => "BOOL",
                     _ => continue, // Skip unsupported types
                 };
-                columns.push(format!("{} {}", key, col_type));
+                columns.push(property_column::<N>(key, col_type));
             }
         }
+        columns.extend(vector_columns::<N>());
         
         let create_table = format!(
             "CREATE NODE TABLE IF NOT EXISTS {} ({}, PRIMARY KEY({}))",
             label,
             columns.join(", "),
-            N::primary_key_field()
+            pk_field
         );
         
         // Execute table creation
         conn.query(&create_table)?;
         
-        // Format properties as individual SET clauses
+        // Format properties as individual SET clauses, with the key as a parameter
+        // unless the database generates it
+        let generated = N::key_generation().is_some();
         let mut props_list = Vec::new();
+        if !generated {
+            props_list.push(format!("{}: $__id", pk_field));
+        }
         if let serde_json::Value::Object(obj) = props {
             for (key, value) in obj {
+                if key == pk_field {
+                    continue;
+                }
                 let value_str = match value {
                     serde_json::Value::String(s) => format!("'{}'", s),
                     serde_json::Value::Number(n) => n.to_string(),
                     serde_json::Value::Bool(b) => b.to_string(),
+                    serde_json::Value::Array(_) => match vector_dim::<N>(&key) {
+                        Some(dim) => format!("CAST({} AS FLOAT[{}])", value, dim),
+                        None => continue,
+                    },
                     _ => continue, // Skip unsupported types
                 };
                 props_list.push(format!("{}: {}", key, value_str));
@@ -132,56 +161,57 @@ impl KuzuGraph {
         
         // First try to delete any existing node with the same ID
         let delete_query = format!(
-            "MATCH (n:{}) WHERE n.{} = '{}' DELETE n",
+            "MATCH (n:{}) WHERE n.{} = $__id DELETE n",
             label,
-            N::primary_key_field(),
-            node.primary_key()
+            pk_field
         );
         
-        conn.query(&delete_query)?;
-        
         // Then create the new node
         let query = format!(
-            "CREATE (:{} {{{}}})",
+            "CREATE (n:{} {{{}}}) RETURN n.{}",
             label,
-            props_list.join(", ")
+            props_list.join(", "),
+            pk_field
         );
         
-        conn.query(&query)?;
-        Ok(())
+        // Check unique properties and write in one transaction
+        self.transaction(|txn| {
+            let conn = txn.connection();
+            self.check_unique(conn, node, true)?;
+            if generated {
+                let result = conn.query(&query)?;
+                return created_key::<N>(result);
+            }
+            for query in [&delete_query, &query] {
+                let mut statement = conn.prepare(query)?;
+                conn.execute(&mut statement, vec![("__id", node.primary_key().to_value())])?;
+            }
+            Ok(node.primary_key())
+        })
     }
 
     /// Find a node by its primary key
-    pub fn find_node<N: Node>(&self, id: &str) -> Result<Option<N>> {
+    ///
+    /// # Arguments
+    /// * `id` - The typed primary key, e.g. a `&str` for STRING keys or a tuple for composite keys
+    pub fn find_node<N: Node>(&self, id: impl Into<N::Key>) -> Result<Option<N>> {
         let label = N::label();
         let pk_field = N::primary_key_field();
         let conn = self.get_connection()?;
         
         let query = format!(
-            "MATCH (n:{}) WHERE n.{} = '{}' RETURN n",
-            label, pk_field, id
+            "MATCH (n:{}) WHERE n.{} = $__id RETURN n",
+            label, pk_field
         );
         
-        let result = conn.query(&query)?;
+        let mut statement = conn.prepare(&query)?;
+        let result = conn.execute(&mut statement, vec![("__id", id.into().to_value())])?;
         let mut rows = result.into_iter();
         
         if let Some(row) = rows.next() {
             if let Value::Node(node) = &row[0] {
-                // Convert node properties to JSON value
-                let mut props = json!({});
-                for (key, value) in node.get_properties() {
-                    props[key] = match value {
-                        Value::String(s) => json!(s),
-                        Value::Int64(n) => json!(n),
-                        Value::UInt64(n) => json!(n),
-                        Value::Double(n) => json!(n),
-                        Value::Bool(b) => json!(b),
-                        _ => continue, // Skip unsupported types
-                    };
-                }
-                
-                // Deserialize from JSON value
-                Ok(Some(serde_json::from_value(props)?))
+                // Convert node properties to JSON value and deserialize
+                Ok(Some(node_from_json(node_to_json(node))?))
             } else {
                 Ok(None)
             }
@@ -190,41 +220,118 @@ impl KuzuGraph {
         }
     }
 
+    /// Update the properties of an existing node, matched by its primary key
+    ///
+    /// Unlike `create_node`, the node keeps its relationships.
+    ///
+    /// # Returns
+    /// * `Result<()>` - `Error::NodeNotFound` if no node has the primary key of `node`
+    pub fn update_node<N: Node>(&self, node: &N) -> Result<()> {
+        node.validate()?;
+        let label = N::label();
+        let pk_field = N::primary_key_field();
+        let (assignments, values) = assignments(node);
+
+        let mut query = format!("MATCH (n:{}) WHERE n.{} = $__id", label, pk_field);
+        if !assignments.is_empty() {
+            query.push_str(&format!(" SET {}", assignments.join(", ")));
+        }
+        query.push_str(" RETURN n");
+
+        let mut params: Vec<(&str, Value)> = values.iter()
+            .map(|(key, value)| (key.as_str(), value.clone()))
+            .collect();
+        params.push(("__id", node.primary_key().to_value()));
+
+        self.transaction(|txn| {
+            let conn = txn.connection();
+            self.check_unique(conn, node, false)?;
+
+            let mut statement = conn.prepare(&query)?;
+            let mut result = conn.execute(&mut statement, params)?;
+            if result.next().is_none() {
+                return Err(Error::NodeNotFound(node.primary_key().to_key_string()));
+            }
+            Ok(())
+        })
+    }
+
+    /// Create a node, or update the properties of the node with the same primary key
+    ///
+    /// Unlike `create_node`, an existing node is updated in place and keeps its relationships.
+    /// Nodes with a generated key (`#[id(auto = "..")]`) cannot be upserted, since the
+    /// database assigns their key; use `create_node` or `update_node` instead.
+    pub fn upsert_node<N: Node>(&self, node: &N) -> Result<()> {
+        node.validate()?;
+        let label = N::label();
+        if N::key_generation().is_some() {
+            return Err(Error::Query(format!(
+                "Cannot upsert {}: its key is generated by the database", label
+            )));
+        }
+        let pk_field = N::primary_key_field();
+        let (assignments, values) = assignments(node);
+
+        let mut query = format!("MERGE (n:{} {{{}: $__id}})", label, pk_field);
+        if !assignments.is_empty() {
+            query.push_str(&format!(" SET {}", assignments.join(", ")));
+        }
+
+        let mut params: Vec<(&str, Value)> = values.iter()
+            .map(|(key, value)| (key.as_str(), value.clone()))
+            .collect();
+        params.push(("__id", node.primary_key().to_value()));
+
+        self.transaction(|txn| {
+            let conn = txn.connection();
+            self.check_unique(conn, node, false)?;
+
+            let mut statement = conn.prepare(&query)?;
+            conn.execute(&mut statement, params)?;
+            Ok(())
+        })
+    }
+
     /// Create a relationship between nodes
+    ///
+    /// The endpoints are matched on the primary key of the node types named in
+    /// `#[from_node(Type)]` and `#[to_node(Type)]`, whatever its column type.
     pub fn create_relationship<R: Relationship>(&self, rel: &R) -> Result<()> {
-        let label = R::label();
-        let props = rel.properties();
-        let conn = self.get_connection()?;
-        
-        // Format properties as individual SET clauses
+        rel.validate()?;
         let mut props_list = Vec::new();
-        if let serde_json::Value::Object(obj) = props {
+        let mut values = Vec::new();
+        if let serde_json::Value::Object(obj) = rel.properties() {
             for (key, value) in obj {
-                // Skip the from/to fields
-                if key == "from" || key == "to" {
+                // The endpoints are matched, not stored
+                if key == R::from_field() || key == R::to_field() {
                     continue;
                 }
-                let value_str = match value {
-                    serde_json::Value::String(s) => format!("'{}'", s),
-                    serde_json::Value::Number(n) => n.to_string(),
-                    serde_json::Value::Bool(b) => b.to_string(),
-                    _ => continue, // Skip unsupported types
-                };
-                props_list.push(format!("{}: {}", key, value_str));
+                if let Some(value) = json_to_value(&value) {
+                    props_list.push(format!("{}: ${}", key, key));
+                    values.push((key, value));
+                }
             }
         }
-        
+
         let query = format!(
-            "MATCH (a:User), (b:User) 
-             WHERE a.id = '{}' AND b.id = '{}'
-             CREATE (a)-[r:{} {{{}}}]->(b)",
-            rel.from(),
-            rel.to(),
-            label,
+            "MATCH (a:{}), (b:{}) WHERE a.{} = $__from AND b.{} = $__to CREATE (a)-[r:{} {{{}}}]->(b)",
+            R::from_node_label(),
+            R::to_node_label(),
+            R::source_key_field(),
+            R::target_key_field(),
+            R::label(),
             props_list.join(", ")
         );
-        
-        conn.query(&query)?;
+
+        let mut params: Vec<(&str, Value)> = values.iter()
+            .map(|(key, value)| (key.as_str(), value.clone()))
+            .collect();
+        params.push(("__from", rel.source_key()));
+        params.push(("__to", rel.target_key()));
+
+        let conn = self.get_connection()?;
+        let mut statement = conn.prepare(&query)?;
+        conn.execute(&mut statement, params)?;
         Ok(())
     }
 
@@ -253,15 +360,21 @@ impl KuzuGraph {
     }
 
     /// Create a node table if it doesn't exist
+    ///
+    /// Properties missing from an existing table are added as new columns, with
+    /// their `#[property(default = ..)]` value for the nodes already stored.
     pub fn create_node_table<N: Node>(&self) -> Result<()> {
         let label = N::label();
         let pk_field = N::primary_key_field();
         let conn = self.get_connection()?;
         
         // Get property types from the Node trait
-        let mut columns = Vec::new();
+        let mut columns = vec![key_column::<N>()];
         if let serde_json::Value::Object(obj) = N::property_types() {
-            for (key, value) in obj {
+            for (key, value) in &obj {
+                if key == pk_field {
+                    continue;
+                }
                 let col_type = match value {
                     serde_json::Value::String(_) => "STRING",
                     serde_json::Value::Number(n) => {
@@ -274,9 +387,10 @@ impl KuzuGraph {
                     serde_json::Value::Bool(_) => "BOOL",
                     _ => continue, // Skip unsupported types
                 };
-                columns.push(format!("{} {}", key, col_type));
+                columns.push(property_column::<N>(key, col_type));
             }
         }
+        columns.extend(vector_columns::<N>());
         
         let query = format!(
             "CREATE NODE TABLE IF NOT EXISTS {} ({}, PRIMARY KEY({}))",
@@ -286,6 +400,8 @@ impl KuzuGraph {
         );
         
         conn.query(&query)?;
+        self.add_missing_columns(label, &columns)?;
+        self.create_declared_indexes::<N>()?;
         Ok(())
     }
 
@@ -317,30 +433,80 @@ impl KuzuGraph {
     }
 
     /// Create a node within a transaction
-    pub fn create_node_in_transaction<N: Node>(&self, txn: &Transaction, node: &N) -> Result<()> {
+    ///
+    /// # Returns
+    /// * `Result<N::Key>` - The primary key of the created node, generated by the database
+    ///   for `#[id(auto = "..")]` keys
+    pub fn create_node_in_transaction<N: Node>(&self, txn: &Transaction, node: &N) -> Result<N::Key> {
+        node.validate()?;
+        self.check_unique(txn.connection(), node, true)?;
         let label = N::label();
+        let pk_field = N::primary_key_field();
         let props = node.properties();
         
-        // Convert properties to a Cypher-compatible format
+        // Convert properties to a Cypher-compatible format, with the key as a parameter
+        // unless the database generates it
+        let generated = N::key_generation().is_some();
+        let key = (!generated).then(|| Ok(format!("{}: $__id", pk_field)));
         let props_str = if let serde_json::Value::Object(obj) = &props {
-            let props: Vec<String> = obj.iter()
-                .map(|(k, v)| {
+            let props: Vec<String> = key.into_iter()
+                .chain(obj.iter().filter(|(k, _)| *k != pk_field).map(|(k, v)| {
                     let value_str = match v {
                         serde_json::Value::String(s) => format!("'{}'", s.replace("'", "\\'")),
                         serde_json::Value::Number(n) => n.to_string(),
                         serde_json::Value::Bool(b) => b.to_string(),
+                        serde_json::Value::Array(_) => match vector_dim::<N>(k) {
+                            Some(dim) => format!("CAST({} AS FLOAT[{}])", v, dim),
+                            None => return Err(Error::InvalidPropertyType("Unsupported property type".into())),
+                        },
                         _ => return Err(Error::InvalidPropertyType("Unsupported property type".into())),
                     };
                     Ok(format!("{}: {}", k, value_str))
-                })
+                }))
                 .collect::<Result<Vec<_>>>()?;
             props.join(", ")
         } else {
             return Err(Error::InvalidPropertyType("Properties must be an object".into()));
         };
 
-        let query = format!("CREATE (n:{} {{{}}}) RETURN n", label, props_str);
-        txn.connection().query(&query)?;
-        Ok(())
+        let query = format!("CREATE (n:{} {{{}}}) RETURN n.{}", label, props_str, pk_field);
+        let mut statement = txn.connection().prepare(&query)?;
+        let params = if generated {
+            Vec::new()
+        } else {
+            vec![("__id", node.primary_key().to_value())]
+        };
+        created_key::<N>(txn.connection().execute(&mut statement, params)?)
+    }
+}
+
+/// Reads the key returned by a `CREATE ... RETURN n.<key>` query
+fn created_key<N: Node>(result: kuzu::QueryResult) -> Result<N::Key> {
+    let row = result
+        .into_iter()
+        .next()
+        .ok_or_else(|| Error::Query(format!("Creating a {} node returned no key", N::label())))?;
+    N::Key::from_value(&row[0])
+}
+
+/// `SET` assignments and parameters for every non-key property of a node
+fn assignments<N: Node>(node: &N) -> (Vec<String>, Vec<(String, Value)>) {
+    let mut assignments = Vec::new();
+    let mut values = Vec::new();
+    if let serde_json::Value::Object(obj) = node.properties() {
+        for (key, value) in obj {
+            if key == N::primary_key_field() {
+                continue;
+            }
+            let value = match vector_dim::<N>(&key) {
+                Some(_) => vector_value(&value),
+                None => json_to_value(&value),
+            };
+            if let Some(value) = value {
+                assignments.push(format!("n.{} = ${}", key, key));
+                values.push((key, value));
+            }
+        }
     }
+    (assignments, values)
 }
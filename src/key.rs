@@ -0,0 +1,160 @@
+use crate::{Result, Error, Node};
+use crate::row::value_to_json;
+use kuzu::Value;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Name of the column holding the synthetic key of nodes with several `#[id]` fields
+pub const COMPOSITE_KEY_FIELD: &str = "_key";
+
+/// A type that can be used as the primary key of a node
+///
+/// Implemented for `String`, `i64` and `Uuid`, and for tuples of those, which are
+/// composite keys stored as a single STRING column.
+pub trait KeyType: Clone + std::fmt::Debug {
+    /// Kuzu column type of the primary key
+    fn column_type() -> &'static str;
+
+    /// Converts the key to a query parameter
+    fn to_value(&self) -> Value;
+
+    /// Renders the key as a string, e.g. for cursors and error messages
+    fn to_key_string(&self) -> String;
+
+    /// Parses a key rendered by `to_key_string`
+    fn from_key_string(key: &str) -> Result<Self>;
+
+    /// Reads a key returned by a query
+    fn from_value(value: &Value) -> Result<Self> {
+        match value_to_json(value) {
+            Some(serde_json::Value::String(s)) => Self::from_key_string(&s),
+            Some(serde_json::Value::Number(n)) => Self::from_key_string(&n.to_string()),
+            _ => Err(Error::InvalidPropertyType(format!("Invalid primary key value: {}", value))),
+        }
+    }
+}
+
+/// How the database generates primary keys declared with `#[id(auto = "..")]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyGeneration {
+    /// An INT64 key counting up from 0, from a `SERIAL` column
+    Serial,
+    /// A random UUID, from a `UUID` column defaulting to `gen_random_uuid()`
+    Uuid,
+}
+
+/// Column definition of the primary key of `N`, including generated keys
+pub(crate) fn key_column<N: Node>() -> String {
+    let definition = match N::key_generation() {
+        Some(KeyGeneration::Serial) => "SERIAL",
+        Some(KeyGeneration::Uuid) => "UUID DEFAULT gen_random_uuid()",
+        None => N::Key::column_type(),
+    };
+    format!("{} {}", N::primary_key_field(), definition)
+}
+
+impl KeyType for String {
+    fn column_type() -> &'static str {
+        "STRING"
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.clone())
+    }
+
+    fn to_key_string(&self) -> String {
+        self.clone()
+    }
+
+    fn from_key_string(key: &str) -> Result<Self> {
+        Ok(key.to_string())
+    }
+}
+
+impl KeyType for i64 {
+    fn column_type() -> &'static str {
+        "INT64"
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Int64(*self)
+    }
+
+    fn to_key_string(&self) -> String {
+        self.to_string()
+    }
+
+    fn from_key_string(key: &str) -> Result<Self> {
+        key.parse().map_err(|_| Error::InvalidPropertyType(format!("Invalid INT64 key: {}", key)))
+    }
+}
+
+impl KeyType for Uuid {
+    fn column_type() -> &'static str {
+        "UUID"
+    }
+
+    fn to_value(&self) -> Value {
+        Value::UUID(*self)
+    }
+
+    fn to_key_string(&self) -> String {
+        self.to_string()
+    }
+
+    fn from_key_string(key: &str) -> Result<Self> {
+        key.parse().map_err(|_| Error::InvalidPropertyType(format!("Invalid UUID key: {}", key)))
+    }
+}
+
+/// Composite keys are stored as the JSON array of their parts
+macro_rules! composite_key {
+    ($($part:ident),+) => {
+        impl<$($part),+> KeyType for ($($part,)+)
+        where
+            $($part: KeyType + Serialize + for<'de> serde::Deserialize<'de>),+
+        {
+            fn column_type() -> &'static str {
+                "STRING"
+            }
+
+            fn to_value(&self) -> Value {
+                Value::String(self.to_key_string())
+            }
+
+            fn to_key_string(&self) -> String {
+                serde_json::to_string(self).unwrap()
+            }
+
+            fn from_key_string(key: &str) -> Result<Self> {
+                Ok(serde_json::from_str(key)?)
+            }
+        }
+    };
+}
+
+composite_key!(A, B);
+composite_key!(A, B, C);
+composite_key!(A, B, C, D);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_strings() {
+        let key = ("acme".to_string(), 42i64);
+        assert_eq!(<(String, i64)>::column_type(), "STRING");
+        assert_eq!(key.to_key_string(), r#"["acme",42]"#);
+        assert_eq!(<(String, i64)>::from_key_string(&key.to_key_string()).unwrap(), key);
+
+        assert_eq!(i64::from_key_string("7").unwrap(), 7);
+        assert!(i64::from_key_string("seven").is_err());
+
+        let id = Uuid::nil();
+        assert_eq!(Uuid::from_key_string(&id.to_key_string()).unwrap(), id);
+        assert!(matches!(id.to_value(), Value::UUID(_)));
+        assert_eq!(Uuid::from_value(&Value::UUID(id)).unwrap(), id);
+        assert_eq!(i64::from_value(&Value::Int64(3)).unwrap(), 3);
+    }
+}
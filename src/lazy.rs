@@ -0,0 +1,192 @@
+use crate::{Result, Error, Node, Relationship, KuzuGraph, FromValue, Direction};
+use kuzu::Value;
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use serde::de::{IgnoredAny, Visitor};
+use std::sync::OnceLock;
+use std::fmt;
+
+/// The node and relationship a `Lazy` field navigates from
+#[derive(Debug, Clone)]
+struct Source {
+    label: &'static str,
+    key_field: &'static str,
+    key: Value,
+    relationship: &'static str,
+    direction: Direction,
+}
+
+/// A relationship navigation field on a node, loaded on demand
+///
+/// Declare it on a `#[derive(Node)]` struct with `#[relation(R, direction = "out")]`.
+/// The field is not stored as a property; nodes read from the graph are bound to
+/// their relationships, and `load` fetches the related nodes the first time it is called.
+///
+/// # Equality
+///
+/// Every `Lazy` compares equal to every other, loaded or not, so that a derived
+/// `PartialEq` on a node compares its properties only. Two nodes whose loaded
+/// relations differ are still equal; compare the results of `get` or `load` to
+/// check the relations themselves.
+pub struct Lazy<T> {
+    source: Option<Source>,
+    value: OnceLock<T>,
+}
+
+impl<T> Lazy<T> {
+    /// Binds the field to the relationships of a node
+    ///
+    /// Called by the `Node` derive; `key` is the primary key of the owning node `N`.
+    #[doc(hidden)]
+    pub fn bind<R: Relationship, N: Node>(&mut self, key: Value, direction: Direction) {
+        self.source = Some(Source {
+            label: N::label(),
+            key_field: N::primary_key_field(),
+            key,
+            relationship: R::label(),
+            direction,
+        });
+        self.value = OnceLock::new();
+    }
+
+    /// The loaded value, if `load` has been called
+    pub fn get(&self) -> Option<&T> {
+        self.value.get()
+    }
+
+    /// Whether the value has been loaded
+    pub fn is_loaded(&self) -> bool {
+        self.value.get().is_some()
+    }
+}
+
+impl<M: Node + FromValue> Lazy<Vec<M>> {
+    /// Fetches the related nodes, or returns them if already loaded
+    ///
+    /// # Arguments
+    /// * `graph` - The graph to load the nodes from
+    ///
+    /// # Returns
+    /// * `Result<&Vec<M>>` - The related nodes, or an error if the field is not bound to a node
+    pub fn load(&self, graph: &KuzuGraph) -> Result<&Vec<M>> {
+        if let Some(value) = self.value.get() {
+            return Ok(value);
+        }
+
+        let source = self.source.as_ref().ok_or_else(|| Error::Query(
+            "Relation field is not bound to a node; read the node from the graph first".into()
+        ))?;
+        let query = format!(
+            "MATCH (a:{}){}(b:{}) WHERE a.{} = $id RETURN b",
+            source.label,
+            source.direction.pattern(&format!(":{}", source.relationship)),
+            M::label(),
+            source.key_field,
+        );
+        let nodes = graph
+            .query_as::<(M,)>(&query, vec![("id", source.key.clone())])?
+            .into_iter()
+            .map(|(node,)| node)
+            .collect();
+
+        Ok(self.value.get_or_init(|| nodes))
+    }
+}
+
+impl<T> Default for Lazy<T> {
+    fn default() -> Self {
+        Self {
+            source: None,
+            value: OnceLock::new(),
+        }
+    }
+}
+
+impl<T: Clone> Clone for Lazy<T> {
+    fn clone(&self) -> Self {
+        Self {
+            source: self.source.clone(),
+            value: self.value.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Lazy<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value.get() {
+            Some(value) => f.debug_tuple("Lazy").field(value).finish(),
+            None => f.write_str("Lazy(<not loaded>)"),
+        }
+    }
+}
+
+/// Always true: relations are ignored by `==`, see [`Lazy`]
+impl<T> PartialEq for Lazy<T> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<T> Serialize for Lazy<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_none()
+    }
+}
+
+/// Deserializes as an unbound field, whether the property is missing, null or present
+impl<'de, T> Deserialize<'de> for Lazy<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct LazyVisitor;
+
+        impl<'de> Visitor<'de> for LazyVisitor {
+            type Value = ();
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a relation field")
+            }
+
+            fn visit_none<E>(self) -> std::result::Result<(), E> {
+                Ok(())
+            }
+
+            fn visit_unit<E>(self) -> std::result::Result<(), E> {
+                Ok(())
+            }
+
+            fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> std::result::Result<(), D::Error> {
+                IgnoredAny::deserialize(deserializer).map(|_| ())
+            }
+        }
+
+        deserializer.deserialize_option(LazyVisitor)?;
+        Ok(Self::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Serialize, Deserialize)]
+    struct Owner {
+        id: String,
+        friends: Lazy<Vec<String>>,
+    }
+
+    #[test]
+    fn test_lazy_is_not_a_property() {
+        let missing: Owner = serde_json::from_value(json!({ "id": "a" })).unwrap();
+        assert!(!missing.friends.is_loaded());
+
+        let present: Owner = serde_json::from_value(json!({ "id": "a", "friends": ["b"] })).unwrap();
+        assert!(present.friends.get().is_none());
+
+        assert_eq!(serde_json::to_value(&missing).unwrap(), json!({ "id": "a", "friends": null }));
+    }
+
+    #[test]
+    fn test_lazy_is_thread_safe() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Owner>();
+    }
+}
@@ -2,21 +2,55 @@
 //! 
 //! This crate provides a high-level, type-safe interface for working with KuzuDB graphs in Rust.
 
+pub mod algo;
+#[cfg(feature = "arrow")]
+mod arrow_batches;
+mod backup;
+mod eager;
 mod error;
 mod graph;
+mod key;
+mod lazy;
+mod path;
+mod optional;
+mod pattern;
 mod query;
+mod row;
+mod schema;
+mod search;
+mod stream;
 mod traits;
+mod traversal;
+pub mod validation;
+mod vector;
 
+#[cfg(feature = "arrow")]
+pub use arrow_batches::{ArrowBatches, batch_to_nodes};
+pub use backup::BackupManifest;
+pub use eager::{EagerQuery, Loaded, LoadedWith};
 pub use error::Error;
 pub use graph::KuzuGraph;
+pub use key::{KeyType, KeyGeneration, COMPOSITE_KEY_FIELD};
+pub use lazy::Lazy;
+pub use stream::NodeStream;
+pub use schema::{IndexKind, IndexInfo};
+pub use row::{Row, FromRow, FromValue, PrimaryKeys, node_from_value, relationship_from_value};
 pub use traits::{Node, Relationship};
+pub use path::{Path, WeightedPath};
+pub use optional::{OptionalQuery, OptionalRow};
+pub use pattern::Pattern;
+pub use query::{Cmp, Order, Field, Selection, QueryBuilder, GroupedQuery, Select, UnionQuery, Page};
+pub use traversal::{Direction, Traversal, TraversalStart};
+pub use validation::{ValidationError, ValidationErrors, HasLength};
+pub use vector::Metric;
 
 // Re-export derive macros and attributes
-pub use okodata_macros::{Node, Relationship};
+pub use okodata_macros::{Node, Relationship, FromRow};
 pub use okodata_macros::{id, property, label, from_node, to_node};
 
 // Re-export important types from kuzu
 pub use kuzu::{Database, SystemConfig, Connection, Value};
+pub use uuid::Uuid;
 
 /// Result type for OkoData operations
 pub type Result<T> = std::result::Result<T, Error>; 
\ No newline at end of file
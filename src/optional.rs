@@ -0,0 +1,93 @@
+use crate::{Result, Node, Relationship, KuzuGraph, FromValue, Direction, QueryBuilder};
+use crate::eager::RelatedQuery;
+use crate::query::Fragment;
+
+/// A root node with one relationship of type `R` and the `M` node at its other end, if any
+pub type OptionalRow<N, R, M> = (N, Option<R>, Option<M>);
+
+/// A query that pairs each matching node with its related nodes, if any,
+/// created by [`QueryBuilder::optional`]
+///
+/// The related part is matched with `OPTIONAL MATCH`, so nodes without a match are
+/// returned once with `None` for the relationship and related node.
+#[derive(Debug)]
+pub struct OptionalQuery<N, R, M> {
+    related: RelatedQuery<N, R, M>,
+    /// WHERE conditions on the optional part, over `r` and `m`
+    conditions: Vec<String>,
+}
+
+impl<N: Node, R: Relationship, M: Node> OptionalQuery<N, R, M> {
+    pub(crate) fn new(query: QueryBuilder<N>) -> Self {
+        Self {
+            related: RelatedQuery::new(query),
+            conditions: Vec::new(),
+        }
+    }
+
+    /// Sets the direction of the relationship to match
+    ///
+    /// # Arguments
+    /// * `direction` - Direction seen from the root nodes, outgoing by default
+    ///
+    /// # Returns
+    /// * Self for method chaining
+    #[must_use]
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.related.direction = direction;
+        self
+    }
+
+    /// Adds a condition to the optional part of the query
+    ///
+    /// Nodes whose related nodes all fail the condition are still returned, with `None`.
+    ///
+    /// # Arguments
+    /// * `condition` - A condition over the relationship `r` and related node `m`
+    ///
+    /// # Returns
+    /// * Self for method chaining
+    #[must_use]
+    pub fn where_clause<S: Into<String>>(mut self, condition: S) -> Self {
+        self.conditions.push(condition.into());
+        self
+    }
+
+    /// Builds the Cypher query string
+    ///
+    /// # Returns
+    /// * A string containing the complete Cypher query
+    pub fn build(&self) -> String {
+        self.render().text()
+    }
+
+    fn render(&self) -> Fragment {
+        let mut query = self.related.render_match();
+
+        if !self.conditions.is_empty() {
+            query.push_str(" WHERE ");
+            query.push_str(&self.conditions.join(" AND "));
+        }
+
+        query.push_str(" RETURN n, r, m");
+        query.push_str(&self.related.query.order_clause());
+        query
+    }
+
+    /// Runs the query and returns each root node with its relationship and related node
+    ///
+    /// # Arguments
+    /// * `graph` - The graph to run the query against
+    ///
+    /// # Returns
+    /// * `Result<Vec<OptionalRow<N, R, M>>>` - One row per match, or per unmatched node
+    pub fn fetch(&self, graph: &KuzuGraph) -> Result<Vec<OptionalRow<N, R, M>>>
+    where
+        N: FromValue,
+        R: FromValue,
+        M: FromValue,
+    {
+        let (query, params) = self.render().finish()?;
+        graph.query_as(&query, params.as_vec())
+    }
+}
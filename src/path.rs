@@ -0,0 +1,75 @@
+use crate::{Result, Error, FromRow, FromValue, PrimaryKeys, Row};
+use kuzu::Value;
+
+/// An ordered walk through the graph: `nodes[i]` and `nodes[i + 1]` are
+/// connected by `relationships[i]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Path<N, R> {
+    /// Nodes along the path, from the start node to the end node
+    pub nodes: Vec<N>,
+    /// Relationships along the path, in traversal order
+    pub relationships: Vec<R>,
+}
+
+impl<N, R> Path<N, R> {
+    /// Number of relationships (hops) in the path
+    pub fn len(&self) -> usize {
+        self.relationships.len()
+    }
+
+    /// Whether the path consists of a single node
+    pub fn is_empty(&self) -> bool {
+        self.relationships.is_empty()
+    }
+
+    /// The node the path starts at
+    pub fn start(&self) -> Option<&N> {
+        self.nodes.first()
+    }
+
+    /// The node the path ends at
+    pub fn end(&self) -> Option<&N> {
+        self.nodes.last()
+    }
+}
+
+/// A path together with its total weight
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedPath<N, R> {
+    /// The nodes and relationships along the path
+    pub path: Path<N, R>,
+    /// Sum of the weights of the path's relationships
+    pub weight: f64,
+}
+
+/// Reads a path from a row of the form `RETURN a, e, b`, where `e` is a
+/// recursive relationship between the start node `a` and the end node `b`
+impl<N: FromValue, R: FromValue> FromRow for Path<N, R> {
+    fn from_row(row: &Row) -> Result<Self> {
+        let (interior, rels) = match row.value(1)? {
+            Value::RecursiveRel { nodes, rels } => (nodes, rels),
+            other => return Err(Error::InvalidPropertyType(
+                format!("Expected a recursive relationship, got {}", other)
+            )),
+        };
+
+        let mut nodes = Vec::with_capacity(interior.len() + 2);
+        nodes.push(row.get_index::<N>(0)?);
+        for node in interior {
+            nodes.push(N::from_value(&Value::Node(node.clone()), row)?);
+        }
+        nodes.push(row.get_index::<N>(2)?);
+
+        let relationships = rels
+            .iter()
+            .map(|rel| R::from_value(&Value::Rel(rel.clone()), row))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Path { nodes, relationships })
+    }
+
+    fn register_keys(keys: &mut PrimaryKeys) {
+        N::register_keys(keys);
+        R::register_keys(keys);
+    }
+}
@@ -0,0 +1,637 @@
+use crate::{Result, Error, Node, Relationship, KuzuGraph, FromRow, PrimaryKeys, Direction};
+use crate::query::{Cmp, Fragment, Params};
+use kuzu::Value;
+
+/// A variable bound by a pattern, with the properties of its table
+#[derive(Debug, Clone)]
+struct Binding {
+    variable: String,
+    label: &'static str,
+    properties: Vec<String>,
+}
+
+/// A builder for `MATCH` patterns spanning several node and relationship types
+///
+/// Each `node` and `rel` call binds a variable to a `Node` or `Relationship` type,
+/// e.g. `(u:User)-[f:FOLLOWS]->(p:Post)<-[:LIKES]-(u2:User)`. Predicates are checked
+/// against the properties of the bound types and rendered as query parameters.
+///
+/// Queries can be chained into stages with `with`, combined with `union`, and
+/// filtered by `exists` and `count` subqueries.
+#[derive(Debug, Clone, Default)]
+pub struct Pattern {
+    /// Clauses of the stages finished by `with`
+    clauses: Vec<Fragment>,
+    /// Rendered pattern elements, alternating nodes and relationships
+    elements: Vec<String>,
+    /// Named variables in the order they were bound
+    bindings: Vec<Binding>,
+    /// List of WHERE conditions, with the values they compare against
+    conditions: Vec<Fragment>,
+    /// Variables or expressions to return; all bound variables if empty
+    returns: Vec<String>,
+    /// Optional LIMIT clause value
+    limit: Option<usize>,
+    /// Primary keys of the bound node types
+    keys: PrimaryKeys,
+    /// Problems found while building, reported by `build`
+    errors: Vec<String>,
+    /// Rendered `UNION` branches appended after the query
+    unions: Vec<Fragment>,
+}
+
+impl Pattern {
+    /// Creates an empty pattern
+    ///
+    /// # Returns
+    /// * A new Pattern instance
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn expects_node(&self) -> bool {
+        self.elements.len().is_multiple_of(2)
+    }
+
+    fn bind(&mut self, variable: &str, label: &'static str, properties: Vec<String>) {
+        if variable.is_empty() || self.binding(variable).is_some() {
+            return;
+        }
+        self.bindings.push(Binding {
+            variable: variable.to_string(),
+            label,
+            properties,
+        });
+    }
+
+    fn binding(&self, variable: &str) -> Option<&Binding> {
+        self.bindings.iter().find(|b| b.variable == variable)
+    }
+
+    /// Adds a node of type `N` to the pattern
+    ///
+    /// # Arguments
+    /// * `variable` - Name to bind the node to, or `""` for an anonymous node
+    ///
+    /// # Returns
+    /// * Self for method chaining
+    #[must_use]
+    pub fn node<N: Node>(mut self, variable: &str) -> Self {
+        if !self.expects_node() {
+            self.errors.push(format!("Node {} must follow a relationship", variable));
+        }
+
+        let properties = match N::property_types() {
+            serde_json::Value::Object(obj) => obj.keys().cloned().collect(),
+            _ => Vec::new(),
+        };
+        self.bind(variable, N::label(), properties);
+        self.keys.register::<N>();
+        self.elements.push(format!("({}:{})", variable, N::label()));
+        self
+    }
+
+    /// Adds a relationship of type `R` to the pattern
+    ///
+    /// # Arguments
+    /// * `variable` - Name to bind the relationship to, or `""` for an anonymous one
+    /// * `direction` - Direction of the relationship, reading the pattern left to right
+    ///
+    /// # Returns
+    /// * Self for method chaining
+    #[must_use]
+    pub fn rel<R: Relationship>(mut self, variable: &str, direction: Direction) -> Self {
+        if self.expects_node() {
+            self.errors.push(format!("Relationship {} must follow a node", variable));
+        }
+
+        let properties = R::property_definitions()
+            .iter()
+            .filter_map(|def| def.split_whitespace().next().map(String::from))
+            .collect();
+        self.bind(variable, R::label(), properties);
+        self.elements.push(direction.pattern(&format!("{}:{}", variable, R::label())));
+        self
+    }
+
+    /// Adds a predicate comparing a property of a bound variable to a value
+    ///
+    /// # Arguments
+    /// * `variable` - A variable bound by `node` or `rel`
+    /// * `field` - A property of the variable's type
+    /// * `cmp` - The comparison to apply
+    /// * `value` - The value to compare against, passed as a query parameter
+    ///
+    /// # Returns
+    /// * Self for method chaining
+    #[must_use]
+    pub fn filter<V: Into<Value>>(mut self, variable: &str, field: &str, cmp: Cmp, value: V) -> Self {
+        match self.binding(variable) {
+            None => self.errors.push(format!("Unknown variable {}", variable)),
+            Some(binding) if !binding.properties.iter().any(|p| p == field) => {
+                self.errors.push(format!("{} has no property {}", binding.label, field));
+            },
+            Some(_) => {
+                let mut condition = Fragment::from(format!("{}.{} {} ", variable, field, cmp.as_str()));
+                condition.push_param(value.into());
+                self.conditions.push(condition);
+            },
+        }
+        self
+    }
+
+    /// Adds a raw WHERE condition to the query
+    ///
+    /// # Arguments
+    /// * `condition` - The condition to add to the WHERE clause
+    ///
+    /// # Returns
+    /// * Self for method chaining
+    #[must_use]
+    pub fn where_clause<S: Into<String>>(mut self, condition: S) -> Self {
+        self.conditions.push(Fragment::from(condition.into()));
+        self
+    }
+
+    /// Adds an `EXISTS { ... }` predicate that holds if the subpattern matches
+    ///
+    /// # Arguments
+    /// * `subpattern` - A pattern that may refer to variables bound by this one
+    ///
+    /// # Returns
+    /// * Self for method chaining
+    #[must_use]
+    pub fn exists(mut self, subpattern: Pattern) -> Self {
+        if let Some(subquery) = self.subquery(&subpattern) {
+            let mut condition = Fragment::from("EXISTS { ".to_string());
+            condition.append(&subquery);
+            condition.push_str(" }");
+            self.conditions.push(condition);
+        }
+        self
+    }
+
+    /// Adds a `COUNT { ... }` predicate comparing the number of subpattern matches to a value
+    ///
+    /// # Arguments
+    /// * `subpattern` - A pattern that may refer to variables bound by this one
+    /// * `cmp` - The comparison to apply
+    /// * `value` - The count to compare against, passed as a query parameter
+    ///
+    /// # Returns
+    /// * Self for method chaining
+    #[must_use]
+    pub fn count(mut self, subpattern: Pattern, cmp: Cmp, value: i64) -> Self {
+        if let Some(subquery) = self.subquery(&subpattern) {
+            let mut condition = Fragment::from("COUNT { ".to_string());
+            condition.append(&subquery);
+            condition.push_str(&format!(" }} {} ", cmp.as_str()));
+            condition.push_param(Value::Int64(value));
+            self.conditions.push(condition);
+        }
+        self
+    }
+
+    /// Renders the MATCH and WHERE clauses of a subpattern, with its parameters
+    fn subquery(&mut self, subpattern: &Pattern) -> Option<Fragment> {
+        match subpattern.render_clauses() {
+            Ok(clauses) => {
+                self.keys.extend(&subpattern.keys);
+                Some(Fragment::join(&clauses, " "))
+            },
+            Err(e) => {
+                self.errors.push(e.to_string());
+                None
+            },
+        }
+    }
+
+    /// Ends the current stage with a `WITH` projection
+    ///
+    /// Nodes and relationships added afterwards start a new `MATCH`, and conditions
+    /// added before them filter the projection with `WITH ... WHERE`. Variables
+    /// projected by name remain bound to their types.
+    ///
+    /// # Arguments
+    /// * `items` - Variables or aliased expressions, e.g. `["u", "COUNT(p) AS posts"]`
+    ///
+    /// # Returns
+    /// * Self for method chaining
+    #[must_use]
+    pub fn with(mut self, items: &[&str]) -> Self {
+        let mut clauses = std::mem::take(&mut self.clauses);
+        if let Err(e) = self.close_stage(&mut clauses) {
+            self.errors.push(e.to_string());
+        }
+        clauses.push(Fragment::from(format!("WITH {}", items.join(", "))));
+        self.clauses = clauses;
+
+        self.elements.clear();
+        self.conditions.clear();
+        self.bindings.retain(|b| items.contains(&b.variable.as_str()));
+        self
+    }
+
+    /// Appends the MATCH and WHERE clauses of the current stage to `clauses`
+    fn close_stage(&self, clauses: &mut Vec<Fragment>) -> Result<()> {
+        if !self.elements.is_empty() {
+            if self.expects_node() {
+                return Err(Error::Query("Pattern must start and end with a node".into()));
+            }
+            clauses.push(Fragment::from(format!("MATCH {}", self.elements.concat())));
+        } else if !self.conditions.is_empty() && clauses.is_empty() {
+            return Err(Error::Query("Conditions must follow a node or a WITH stage".into()));
+        }
+
+        if !self.conditions.is_empty() {
+            let mut clause = Fragment::from("WHERE ".to_string());
+            clause.append(&Fragment::join(&self.conditions, " AND "));
+            clauses.push(clause);
+        }
+        Ok(())
+    }
+
+    /// Renders the clauses of this pattern for use inside `EXISTS { ... }` or `COUNT { ... }`
+    ///
+    /// An invalid pattern is reported when the enclosing query runs.
+    pub(crate) fn subquery_clauses(&self) -> Fragment {
+        match self.render_clauses() {
+            Ok(clauses) => Fragment::join(&clauses, " "),
+            Err(e) => Fragment::error(e.to_string()),
+        }
+    }
+
+    /// Starts a pattern after a `WITH` stage rendered elsewhere, binding `n` to `N`
+    /// if it is projected
+    pub(crate) fn after_stage<N: Node>(stage: Fragment, items: &[&str]) -> Pattern {
+        let mut pattern = Pattern::new().node::<N>("n");
+        pattern.elements.clear();
+        pattern.clauses.push(stage);
+        pattern.bindings.retain(|b| items.contains(&b.variable.as_str()));
+        pattern
+    }
+
+    /// Renders every clause up to, but not including, RETURN
+    fn render_clauses(&self) -> Result<Vec<Fragment>> {
+        if let Some(error) = self.errors.first() {
+            return Err(Error::Query(error.clone()));
+        }
+
+        let mut clauses = self.clauses.clone();
+        self.close_stage(&mut clauses)?;
+        if clauses.is_empty() {
+            return Err(Error::Query("Pattern must start and end with a node".into()));
+        }
+        Ok(clauses)
+    }
+
+    /// Combines this query with another one, removing duplicate rows
+    ///
+    /// # Arguments
+    /// * `other` - A query returning the same columns
+    ///
+    /// # Returns
+    /// * Self for method chaining
+    #[must_use]
+    pub fn union(self, other: Pattern) -> Self {
+        self.combine("UNION", other)
+    }
+
+    /// Combines this query with another one, keeping duplicate rows
+    ///
+    /// # Arguments
+    /// * `other` - A query returning the same columns
+    ///
+    /// # Returns
+    /// * Self for method chaining
+    #[must_use]
+    pub fn union_all(self, other: Pattern) -> Self {
+        self.combine("UNION ALL", other)
+    }
+
+    fn combine(mut self, keyword: &str, other: Pattern) -> Self {
+        if self.return_items().len() != other.return_items().len() {
+            self.errors.push(format!(
+                "{} branches must return the same number of columns", keyword
+            ));
+            return self;
+        }
+
+        match other.query() {
+            Ok(query) => {
+                self.keys.extend(&other.keys);
+                let mut branch = Fragment::from(format!(" {} ", keyword));
+                branch.append(&query);
+                self.unions.push(branch);
+            },
+            Err(e) => self.errors.push(e.to_string()),
+        }
+        self
+    }
+
+    /// The variables or expressions the query returns
+    fn return_items(&self) -> Vec<&str> {
+        if self.returns.is_empty() {
+            self.bindings.iter().map(|b| b.variable.as_str()).collect()
+        } else {
+            self.returns.iter().map(String::as_str).collect()
+        }
+    }
+
+    /// Sets what the query returns, in order
+    ///
+    /// # Arguments
+    /// * `items` - Variables or expressions, e.g. `["u", "f", "p.title"]`
+    ///
+    /// # Returns
+    /// * Self for method chaining
+    #[must_use]
+    pub fn returns(mut self, items: &[&str]) -> Self {
+        self.returns = items.iter().map(|i| i.to_string()).collect();
+        self
+    }
+
+    /// Limits the number of results
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum number of results to return
+    ///
+    /// # Returns
+    /// * Self for method chaining
+    #[must_use]
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Builds the Cypher query string
+    ///
+    /// # Returns
+    /// * `Result<String>` - The complete query, or an error describing an invalid pattern
+    pub fn build(&self) -> Result<String> {
+        Ok(self.query()?.render(&mut Params::default()))
+    }
+
+    /// The complete query, with its parameter values in place
+    fn query(&self) -> Result<Fragment> {
+        let mut query = Fragment::join(&self.render_clauses()?, " ");
+
+        query.push_str(&format!(" RETURN {}", self.return_items().join(", ")));
+
+        if let Some(limit) = self.limit {
+            query.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        query.append(&Fragment::join(&self.unions, ""));
+        Ok(query)
+    }
+
+    /// Runs the pattern and maps every row onto `T`, typically a tuple of the returned types
+    ///
+    /// # Returns
+    /// * `Result<Vec<T>>` - The mapped rows or an error
+    pub fn fetch<T: FromRow>(&self, graph: &KuzuGraph) -> Result<Vec<T>> {
+        let mut params = Params::default();
+        let query = self.query()?.render(&mut params);
+        graph.query_as_with_keys(&query, params.as_vec(), self.keys.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Serialize, Deserialize};
+    use serde_json::json;
+
+    #[derive(Serialize, Deserialize)]
+    struct User {
+        id: String,
+        age: i64,
+    }
+
+    impl Node for User {
+        fn label() -> &'static str {
+            "User"
+        }
+
+        type Key = String;
+
+        fn primary_key_field() -> &'static str {
+            "id"
+        }
+
+        fn primary_key(&self) -> String {
+            self.id.clone()
+        }
+
+        fn property_types() -> serde_json::Value {
+            json!({
+                "id": "",
+                "age": 0
+            })
+        }
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Post {
+        id: String,
+    }
+
+    impl Node for Post {
+        fn label() -> &'static str {
+            "Post"
+        }
+
+        type Key = String;
+
+        fn primary_key_field() -> &'static str {
+            "id"
+        }
+
+        fn primary_key(&self) -> String {
+            self.id.clone()
+        }
+
+        fn property_types() -> serde_json::Value {
+            json!({ "id": "" })
+        }
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Likes {
+        from: String,
+        to: String,
+        since: i64,
+    }
+
+    impl Relationship for Likes {
+        fn label() -> &'static str {
+            "LIKES"
+        }
+
+        fn from(&self) -> String {
+            self.from.clone()
+        }
+
+        fn to(&self) -> String {
+            self.to.clone()
+        }
+
+        fn from_field() -> &'static str {
+            "from"
+        }
+
+        fn to_field() -> &'static str {
+            "to"
+        }
+
+        fn from_node_label() -> &'static str {
+            "User"
+        }
+
+        fn to_node_label() -> &'static str {
+            "Post"
+        }
+
+        fn property_definitions() -> Vec<String> {
+            vec!["since INT64".to_string()]
+        }
+    }
+
+    #[test]
+    fn test_pattern_builder() {
+        let query = Pattern::new()
+            .node::<User>("u")
+            .rel::<Likes>("l", Direction::Outgoing)
+            .node::<Post>("p")
+            .rel::<Likes>("", Direction::Incoming)
+            .node::<User>("u2")
+            .filter("u", "age", Cmp::Gt, 18)
+            .filter("l", "since", Cmp::Ge, 2020)
+            .returns(&["u", "p", "u2"])
+            .limit(10)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            query,
+            "MATCH (u:User)-[l:LIKES]->(p:Post)<-[:LIKES]-(u2:User) \
+             WHERE u.age > $p0 AND l.since >= $p1 RETURN u, p, u2 LIMIT 10"
+        );
+    }
+
+    #[test]
+    fn test_pattern_returns_bound_variables_by_default() {
+        let query = Pattern::new()
+            .node::<User>("u")
+            .rel::<Likes>("l", Direction::Outgoing)
+            .node::<Post>("")
+            .build()
+            .unwrap();
+
+        assert_eq!(query, "MATCH (u:User)-[l:LIKES]->(:Post) RETURN u, l");
+    }
+
+    #[test]
+    fn test_pattern_rejects_unknown_property() {
+        let result = Pattern::new()
+            .node::<User>("u")
+            .filter("u", "name", Cmp::Eq, "Alice")
+            .build();
+
+        assert!(matches!(result, Err(Error::Query(_))));
+    }
+
+    #[test]
+    fn test_pattern_must_end_with_node() {
+        let result = Pattern::new()
+            .node::<User>("u")
+            .rel::<Likes>("l", Direction::Outgoing)
+            .build();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_pattern_with_stages() {
+        let query = Pattern::new()
+            .node::<User>("u")
+            .filter("u", "age", Cmp::Ge, 18)
+            .rel::<Likes>("", Direction::Outgoing)
+            .node::<Post>("p")
+            .with(&["u", "COUNT(p) AS likes"])
+            .where_clause("likes > 2")
+            .filter("u", "age", Cmp::Lt, 65)
+            .returns(&["u.id", "likes"])
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            query,
+            "MATCH (u:User)-[:LIKES]->(p:Post) WHERE u.age >= $p0 \
+             WITH u, COUNT(p) AS likes WHERE likes > 2 AND u.age < $p1 RETURN u.id, likes"
+        );
+    }
+
+    #[test]
+    fn test_pattern_subqueries() {
+        let query = Pattern::new()
+            .node::<User>("u")
+            .filter("u", "age", Cmp::Gt, 18)
+            .exists(
+                Pattern::new()
+                    .node::<User>("u")
+                    .rel::<Likes>("l", Direction::Outgoing)
+                    .node::<Post>("")
+                    .filter("l", "since", Cmp::Ge, 2020),
+            )
+            .count(
+                Pattern::new()
+                    .node::<User>("u")
+                    .rel::<Likes>("", Direction::Outgoing)
+                    .node::<Post>(""),
+                Cmp::Lt,
+                10,
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            query,
+            "MATCH (u:User) WHERE u.age > $p0 \
+             AND EXISTS { MATCH (u:User)-[l:LIKES]->(:Post) WHERE l.since >= $p1 } \
+             AND COUNT { MATCH (u:User)-[:LIKES]->(:Post) } < $p2 RETURN u"
+        );
+    }
+
+    #[test]
+    fn test_pattern_union() {
+        let young = Pattern::new().node::<User>("u").filter("u", "age", Cmp::Lt, 18);
+        let old = Pattern::new().node::<User>("u").filter("u", "age", Cmp::Gt, 65);
+
+        let query = young.clone().union_all(old).build().unwrap();
+        assert_eq!(
+            query,
+            "MATCH (u:User) WHERE u.age < $p0 RETURN u \
+             UNION ALL MATCH (u:User) WHERE u.age > $p1 RETURN u"
+        );
+
+        // Text that looks like a placeholder is left alone when branches are combined
+        let literal = Pattern::new().node::<User>("u").where_clause("u.id <> '$p0'");
+        let query = young.clone().union(literal).build().unwrap();
+        assert_eq!(
+            query,
+            "MATCH (u:User) WHERE u.age < $p0 RETURN u \
+             UNION MATCH (u:User) WHERE u.id <> '$p0' RETURN u"
+        );
+
+        let mismatched = young.union(
+            Pattern::new()
+                .node::<User>("a")
+                .rel::<Likes>("l", Direction::Outgoing)
+                .node::<Post>("b"),
+        );
+        assert!(mismatched.build().is_err());
+    }
+}
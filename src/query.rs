@@ -1,23 +1,295 @@
-#[cfg(test)]
-use crate::Node;
-#[cfg(test)]
+use crate::{Result, Error, Node, Relationship, KuzuGraph, FromRow, FromValue, EagerQuery, OptionalQuery, Pattern, KeyType};
+use crate::row::{json_to_value, value_to_json};
+use kuzu::Value;
+use std::collections::HashMap;
+use std::hash::Hash;
 use std::marker::PhantomData;
 
+/// Comparison operator for a typed WHERE predicate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cmp {
+    /// `=`
+    Eq,
+    /// `<>`
+    Ne,
+    /// `<`
+    Lt,
+    /// `<=`
+    Le,
+    /// `>`
+    Gt,
+    /// `>=`
+    Ge,
+    /// `CONTAINS`, for strings
+    Contains,
+    /// `STARTS WITH`, for strings
+    StartsWith,
+    /// `ENDS WITH`, for strings
+    EndsWith,
+}
+
+impl Cmp {
+    /// The Cypher operator for this comparison
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Cmp::Eq => "=",
+            Cmp::Ne => "<>",
+            Cmp::Lt => "<",
+            Cmp::Le => "<=",
+            Cmp::Gt => ">",
+            Cmp::Ge => ">=",
+            Cmp::Contains => "CONTAINS",
+            Cmp::StartsWith => "STARTS WITH",
+            Cmp::EndsWith => "ENDS WITH",
+        }
+    }
+}
+
+/// Query parameters collected while building a query, named `$p0`, `$p1`, ...
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Params {
+    values: Vec<(String, Value)>,
+}
+
+impl Params {
+    /// Stores a value and returns the placeholder that refers to it
+    pub(crate) fn push(&mut self, value: Value) -> String {
+        let name = format!("p{}", self.values.len());
+        let placeholder = format!("${}", name);
+        self.values.push((name, value));
+        placeholder
+    }
+
+    /// The parameters in the form expected by kuzu's `execute`
+    pub(crate) fn as_vec(&self) -> Vec<(&str, Value)> {
+        self.values.iter().map(|(name, value)| (name.as_str(), value.clone())).collect()
+    }
+}
+
+/// A piece of query text with parameter values embedded where their placeholders go
+///
+/// Fragments can be combined freely; placeholders are only named when the
+/// fragment is rendered, so combined queries never need renumbering.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Fragment {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone)]
+enum Part {
+    Text(String),
+    Param(Value),
+    /// A part that could not be built, reported when the query runs
+    Error(String),
+}
+
+impl Fragment {
+    /// Appends query text
+    pub(crate) fn push_str(&mut self, text: &str) {
+        match self.parts.last_mut() {
+            Some(Part::Text(last)) => last.push_str(text),
+            _ => self.parts.push(Part::Text(text.to_string())),
+        }
+    }
+
+    /// Appends a parameter value
+    pub(crate) fn push_param(&mut self, value: Value) {
+        self.parts.push(Part::Param(value));
+    }
+
+    /// A fragment standing in for a part of the query that could not be built
+    pub(crate) fn error(message: String) -> Self {
+        Fragment { parts: vec![Part::Error(message)] }
+    }
+
+    /// Appends another fragment
+    pub(crate) fn append(&mut self, other: &Fragment) {
+        for part in &other.parts {
+            match part {
+                Part::Text(text) => self.push_str(text),
+                part => self.parts.push(part.clone()),
+            }
+        }
+    }
+
+    /// Joins fragments with a separator
+    pub(crate) fn join(fragments: &[Fragment], separator: &str) -> Fragment {
+        let mut joined = Fragment::default();
+        for (i, fragment) in fragments.iter().enumerate() {
+            if i > 0 {
+                joined.push_str(separator);
+            }
+            joined.append(fragment);
+        }
+        joined
+    }
+
+    /// Renders the text, storing each value in `params` in place of its placeholder
+    ///
+    /// Parts that could not be built are rendered as comments.
+    pub(crate) fn render(&self, params: &mut Params) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                Part::Text(text) => text.clone(),
+                Part::Param(value) => params.push(value.clone()),
+                Part::Error(message) => format!("/* {} */", message),
+            })
+            .collect()
+    }
+
+    /// Renders the query text for display, naming its parameters from `$p0`
+    pub(crate) fn text(&self) -> String {
+        self.render(&mut Params::default())
+    }
+
+    /// Renders a complete query to run, naming its parameters from `$p0`
+    ///
+    /// # Returns
+    /// * `Result<(String, Params)>` - The query and its parameters, or the first part that could not be built
+    pub(crate) fn finish(&self) -> Result<(String, Params)> {
+        if let Some(Part::Error(message)) = self.parts.iter().find(|part| matches!(part, Part::Error(_))) {
+            return Err(Error::Query(message.clone()));
+        }
+        let mut params = Params::default();
+        let text = self.render(&mut params);
+        Ok((text, params))
+    }
+}
+
+impl From<String> for Fragment {
+    fn from(text: String) -> Self {
+        Fragment { parts: vec![Part::Text(text)] }
+    }
+}
+
+impl From<&str> for Fragment {
+    fn from(text: &str) -> Self {
+        Fragment::from(text.to_string())
+    }
+}
+
+/// Sort direction for [`QueryBuilder::order_by`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Order {
+    /// Smallest values first
+    #[default]
+    Asc,
+    /// Largest values first
+    Desc,
+}
+
+impl Order {
+    /// The Cypher keyword for this direction
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Order::Asc => "ASC",
+            Order::Desc => "DESC",
+        }
+    }
+}
+
+/// A typed reference to a property of a node type, generated by the `Node` derive
+/// as an associated constant of a companion type, e.g. `UserFields::name`
+pub struct Field<N, T> {
+    name: &'static str,
+    types: PhantomData<fn() -> (N, T)>,
+}
+
+impl<N, T> Field<N, T> {
+    /// Creates a reference to the property `name`
+    pub const fn new(name: &'static str) -> Self {
+        Self { name, types: PhantomData }
+    }
+
+    /// The name of the property
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<N, T> Clone for Field<N, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<N, T> Copy for Field<N, T> {}
+
+impl<N, T> std::fmt::Debug for Field<N, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Field").field(&self.name).finish()
+    }
+}
+
+/// One or more fields of `N` to project, with the type of the resulting rows
+///
+/// Implemented for a single [`Field`] and for tuples of up to six fields.
+pub trait Selection<N> {
+    /// The row type produced by the projection
+    type Output: FromRow;
+
+    /// The names of the projected properties, in order
+    fn fields(&self) -> Vec<&'static str>;
+}
+
+impl<N, A: FromValue> Selection<N> for Field<N, A> {
+    type Output = (A,);
+
+    fn fields(&self) -> Vec<&'static str> {
+        vec![self.name]
+    }
+}
+
+macro_rules! impl_selection_tuple {
+    ($($ty:ident => $idx:tt),+) => {
+        impl<N, $($ty: FromValue),+> Selection<N> for ($(Field<N, $ty>,)+) {
+            type Output = ($($ty,)+);
+
+            fn fields(&self) -> Vec<&'static str> {
+                vec![$(self.$idx.name),+]
+            }
+        }
+    };
+}
+
+impl_selection_tuple!(A => 0);
+impl_selection_tuple!(A => 0, B => 1);
+impl_selection_tuple!(A => 0, B => 1, C => 2);
+impl_selection_tuple!(A => 0, B => 1, C => 2, D => 3);
+impl_selection_tuple!(A => 0, B => 1, C => 2, D => 3, E => 4);
+impl_selection_tuple!(A => 0, B => 1, C => 2, D => 3, E => 4, F => 5);
+
 /// A builder for constructing type-safe Cypher queries
-#[derive(Default, Debug, Clone)]
-#[cfg(test)]
+#[derive(Default, Debug)]
 pub struct QueryBuilder<N> {
     /// Phantom data to hold the node type
     node_type: PhantomData<N>,
-    /// List of WHERE conditions
-    conditions: Vec<String>,
+    /// List of WHERE conditions, with the values they compare against
+    conditions: Vec<Fragment>,
     /// Optional LIMIT clause value
     limit: Option<usize>,
     /// Optional SKIP clause value
     skip: Option<usize>,
+    /// Properties to order the results by, with their directions
+    order: Vec<(String, Order)>,
+    /// Whether to return only distinct rows
+    distinct: bool,
+}
+
+impl<N> Clone for QueryBuilder<N> {
+    fn clone(&self) -> Self {
+        Self {
+            node_type: PhantomData,
+            conditions: self.conditions.clone(),
+            limit: self.limit,
+            skip: self.skip,
+            order: self.order.clone(),
+            distinct: self.distinct,
+        }
+    }
 }
 
-#[cfg(test)]
 impl<N: Node> QueryBuilder<N> {
     /// Creates a new query builder for the given node type
     /// 
@@ -30,6 +302,8 @@ impl<N: Node> QueryBuilder<N> {
             conditions: Vec::new(),
             limit: None,
             skip: None,
+            order: Vec::new(),
+            distinct: false,
         }
     }
 
@@ -42,7 +316,7 @@ impl<N: Node> QueryBuilder<N> {
     /// * Self for method chaining
     #[must_use]
     pub fn where_clause<S: Into<String>>(mut self, condition: S) -> Self {
-        self.conditions.push(condition.into());
+        self.conditions.push(Fragment::from(condition.into()));
         self
     }
 
@@ -72,19 +346,195 @@ impl<N: Node> QueryBuilder<N> {
         self
     }
 
+    /// Orders the results by a property; chain calls to break ties with further properties
+    ///
+    /// # Arguments
+    /// * `field` - The property to order by
+    /// * `order` - Whether to sort ascending or descending
+    ///
+    /// # Returns
+    /// * Self for method chaining
+    #[must_use]
+    pub fn order_by(mut self, field: &str, order: Order) -> Self {
+        self.order.push((field.to_string(), order));
+        self
+    }
+
+    /// Returns only distinct rows
+    ///
+    /// # Returns
+    /// * Self for method chaining
+    #[must_use]
+    pub fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+
+    /// Projects the results onto some of the node's properties
+    ///
+    /// # Arguments
+    /// * `selection` - A field or tuple of fields, e.g. `(UserFields::name, UserFields::age)`
+    ///
+    /// # Returns
+    /// * A Select query returning a tuple per row
+    #[must_use]
+    pub fn select<S: Selection<N>>(self, selection: S) -> Select<N, S> {
+        Select {
+            fields: selection.fields(),
+            query: self,
+            selection: PhantomData,
+        }
+    }
+
+    /// Eagerly loads the nodes related to each result through `R` relationships
+    ///
+    /// Relationships are followed outgoing unless [`EagerQuery::direction`] says otherwise.
+    ///
+    /// # Returns
+    /// * An EagerQuery that fetches each node with its relationships and neighbours
+    #[must_use]
+    pub fn with<R: Relationship, M: Node>(self) -> EagerQuery<N, R, M> {
+        EagerQuery::new(self)
+    }
+
+    /// Pairs each result with the nodes related through `R` relationships, if any
+    ///
+    /// Relationships are followed outgoing unless [`OptionalQuery::direction`] says otherwise.
+    ///
+    /// # Returns
+    /// * An OptionalQuery that matches the related part with OPTIONAL MATCH
+    #[must_use]
+    pub fn optional<R: Relationship, M: Node>(self) -> OptionalQuery<N, R, M> {
+        OptionalQuery::new(self)
+    }
+
+    /// Adds an `EXISTS { ... }` condition that holds if the subpattern matches
+    ///
+    /// # Arguments
+    /// * `subpattern` - A pattern that may refer to the matched node as `n`
+    ///
+    /// # Returns
+    /// * Self for method chaining; an invalid subpattern is reported when the query runs
+    #[must_use]
+    pub fn exists(mut self, subpattern: Pattern) -> Self {
+        let mut condition = Fragment::from("EXISTS { ");
+        condition.append(&subpattern.subquery_clauses());
+        condition.push_str(" }");
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Adds a `COUNT { ... }` condition comparing the number of subpattern matches to a value
+    ///
+    /// # Arguments
+    /// * `subpattern` - A pattern that may refer to the matched node as `n`
+    /// * `cmp` - The comparison to apply
+    /// * `value` - The count to compare against, passed as a query parameter
+    ///
+    /// # Returns
+    /// * Self for method chaining; an invalid subpattern is reported when the query runs
+    #[must_use]
+    pub fn count_matches(mut self, subpattern: Pattern, cmp: Cmp, value: i64) -> Self {
+        let mut condition = Fragment::from("COUNT { ");
+        condition.append(&subpattern.subquery_clauses());
+        condition.push_str(&format!(" }} {} ", cmp.as_str()));
+        condition.push_param(Value::Int64(value));
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Combines the results with those of another query, removing duplicates
+    ///
+    /// # Returns
+    /// * A UnionQuery returning the nodes matched by either query
+    #[must_use]
+    pub fn union(self, other: QueryBuilder<N>) -> UnionQuery<N> {
+        UnionQuery::new(self).union(other)
+    }
+
+    /// Combines the results with those of another query, keeping duplicates
+    ///
+    /// # Returns
+    /// * A UnionQuery returning the nodes matched by both queries
+    #[must_use]
+    pub fn union_all(self, other: QueryBuilder<N>) -> UnionQuery<N> {
+        UnionQuery::new(self).union_all(other)
+    }
+
+    /// Ends the query with a `WITH` projection and continues it as a [`Pattern`]
+    ///
+    /// ORDER BY, SKIP and LIMIT apply to the projection. If `n` is projected, it stays
+    /// bound to `N` in the returned pattern.
+    ///
+    /// # Arguments
+    /// * `items` - Variables or aliased expressions, e.g. `["n", "COUNT(*) AS total"]`
+    ///
+    /// # Returns
+    /// * A Pattern whose next `node` and `rel` calls start a new `MATCH`
+    #[must_use]
+    pub fn project(self, items: &[&str]) -> Pattern {
+        let stage = self.render_stage("WITH", items.join(", ").into(), &self.order_clause());
+        Pattern::after_stage::<N>(stage, items)
+    }
+
+    /// Groups aggregations by a property of the node
+    ///
+    /// # Arguments
+    /// * `field` - The property whose values become the keys of the results
+    ///
+    /// # Returns
+    /// * A GroupedQuery whose aggregations return one value per key
+    #[must_use]
+    pub fn group_by<K: FromValue + Eq + Hash>(self, field: &str) -> GroupedQuery<N, K> {
+        GroupedQuery {
+            query: self,
+            field: field.to_string(),
+            key_type: PhantomData,
+        }
+    }
+
     /// Builds the Cypher query string
     /// 
     /// # Returns
     /// * A string containing the complete Cypher query
     pub fn build(&self) -> String {
-        let mut query = format!("MATCH (n:{})", N::label());
+        self.render("n", true).text()
+    }
+
+    /// Renders the query with the given RETURN items, applying ORDER BY if `ordered`
+    fn render(&self, returns: &str, ordered: bool) -> Fragment {
+        let order = if ordered { self.order_clause() } else { String::new() };
+        self.render_stage("RETURN", returns.into(), &order)
+    }
+
+    /// Renders an aggregating RETURN over the matching nodes
+    ///
+    /// SKIP and LIMIT select the nodes to aggregate, so they are applied in a
+    /// `WITH n` stage before the aggregation rather than to its result.
+    fn render_aggregate(&self, returns: &str) -> Fragment {
+        if self.skip.is_none() && self.limit.is_none() {
+            return self.render(returns, false);
+        }
+        let mut query = self.render_stage("WITH", "n".into(), &self.order_clause());
+        query.push_str(&format!(" RETURN {}", returns));
+        query
+    }
+
+    /// Renders the MATCH and WHERE clauses followed by a RETURN or WITH projection,
+    /// the given ORDER BY clause, SKIP and LIMIT
+    pub(crate) fn render_stage(&self, keyword: &str, items: Fragment, order: &str) -> Fragment {
+        let mut query = Fragment::from(format!("MATCH (n:{})", N::label()));
         
         if !self.conditions.is_empty() {
             query.push_str(" WHERE ");
-            query.push_str(&self.conditions.join(" AND "));
+            query.append(&Fragment::join(&self.conditions, " AND "));
         }
         
-        query.push_str(" RETURN n");
+        let distinct = if self.distinct { "DISTINCT " } else { "" };
+        query.push_str(&format!(" {} {}", keyword, distinct));
+        query.append(&items);
+
+        query.push_str(order);
         
         if let Some(skip) = self.skip {
             query.push_str(&format!(" SKIP {}", skip));
@@ -96,11 +546,471 @@ impl<N: Node> QueryBuilder<N> {
         
         query
     }
+
+    /// Renders the ORDER BY clause, or an empty string if no order was given
+    pub(crate) fn order_clause(&self) -> String {
+        if self.order.is_empty() {
+            return String::new();
+        }
+        let order: Vec<String> = self.order
+            .iter()
+            .map(|(field, order)| format!("n.{} {}", field, order.as_str()))
+            .collect();
+        format!(" ORDER BY {}", order.join(", "))
+    }
+
+    /// Resolves a property of `N` to its expression on the matched node
+    fn property(field: &str) -> Result<String> {
+        match N::property_types() {
+            serde_json::Value::Object(obj) if obj.contains_key(field) => Ok(format!("n.{}", field)),
+            _ => Err(Error::Query(format!("{} has no property {}", N::label(), field))),
+        }
+    }
+
+    /// Runs the query and returns the matching nodes
+    ///
+    /// # Arguments
+    /// * `graph` - The graph to run the query against
+    ///
+    /// # Returns
+    /// * `Result<Vec<N>>` - The matching nodes or an error
+    pub fn fetch(&self, graph: &KuzuGraph) -> Result<Vec<N>>
+    where
+        N: FromValue,
+    {
+        let (query, params) = self.render("n", true).finish()?;
+        let rows = graph.query_as::<(N,)>(&query, params.as_vec())?;
+        Ok(rows.into_iter().map(|(node,)| node).collect())
+    }
+
+    /// Runs a single aggregate expression over the matching nodes
+    fn aggregate<T: FromValue>(&self, graph: &KuzuGraph, expression: &str) -> Result<T> {
+        let (query, params) = self.render_aggregate(&format!("{} AS value", expression)).finish()?;
+        graph
+            .query_as::<(T,)>(&query, params.as_vec())?
+            .into_iter()
+            .next()
+            .map(|(value,)| value)
+            .ok_or_else(|| Error::Query(format!("Aggregation returned no rows: {}", query)))
+    }
+
+    /// Counts the matching nodes
+    ///
+    /// # Returns
+    /// * `Result<i64>` - The number of matching nodes or an error
+    pub fn count(&self, graph: &KuzuGraph) -> Result<i64> {
+        self.aggregate(graph, "COUNT(*)")
+    }
+
+    /// Sums a property over the matching nodes
+    ///
+    /// # Arguments
+    /// * `field` - The numeric property to sum
+    ///
+    /// # Returns
+    /// * `Result<T>` - The sum; use `Option<T>` when no nodes may match
+    pub fn sum<T: FromValue>(&self, graph: &KuzuGraph, field: &str) -> Result<T> {
+        self.aggregate(graph, &format!("SUM({})", Self::property(field)?))
+    }
+
+    /// Averages a property over the matching nodes
+    ///
+    /// # Arguments
+    /// * `field` - The numeric property to average
+    ///
+    /// # Returns
+    /// * `Result<Option<f64>>` - The average, or None if no nodes match
+    pub fn avg(&self, graph: &KuzuGraph, field: &str) -> Result<Option<f64>> {
+        self.aggregate(graph, &format!("AVG({})", Self::property(field)?))
+    }
+
+    /// Finds the smallest value of a property over the matching nodes
+    ///
+    /// # Arguments
+    /// * `field` - The property to compare
+    ///
+    /// # Returns
+    /// * `Result<Option<T>>` - The minimum, or None if no nodes match
+    pub fn min<T: FromValue>(&self, graph: &KuzuGraph, field: &str) -> Result<Option<T>> {
+        self.aggregate(graph, &format!("MIN({})", Self::property(field)?))
+    }
+
+    /// Finds the largest value of a property over the matching nodes
+    ///
+    /// # Arguments
+    /// * `field` - The property to compare
+    ///
+    /// # Returns
+    /// * `Result<Option<T>>` - The maximum, or None if no nodes match
+    pub fn max<T: FromValue>(&self, graph: &KuzuGraph, field: &str) -> Result<Option<T>> {
+        self.aggregate(graph, &format!("MAX({})", Self::property(field)?))
+    }
+
+    /// Collects the values of a property over the matching nodes
+    ///
+    /// # Arguments
+    /// * `field` - The property to collect
+    ///
+    /// # Returns
+    /// * `Result<Vec<T>>` - The collected values or an error
+    pub fn collect<T: FromValue>(&self, graph: &KuzuGraph, field: &str) -> Result<Vec<T>> {
+        self.aggregate(graph, &format!("COLLECT({})", Self::property(field)?))
+    }
+
+    /// Fetches the page of results that follows a cursor, using keyset pagination
+    ///
+    /// Results are ordered by the first `order_by` property, or by the primary key if
+    /// none was given, with the primary key breaking ties in the same direction.
+    /// Nodes whose sort property is null come last in ascending order and first in
+    /// descending order. SKIP and LIMIT are ignored.
+    ///
+    /// # Arguments
+    /// * `graph` - The graph to run the query against
+    /// * `cursor` - The `next_cursor` of the previous page, or None for the first page
+    /// * `size` - Maximum number of items in the page
+    ///
+    /// # Returns
+    /// * `Result<Page<N>>` - The page of results or an error
+    pub fn page_after(&self, graph: &KuzuGraph, cursor: Option<&str>, size: usize) -> Result<Page<N>>
+    where
+        N: FromValue,
+    {
+        let pk = N::primary_key_field();
+        let (field, order) = self.order
+            .first()
+            .map(|(field, order)| (field.as_str(), *order))
+            .unwrap_or((pk, Order::Asc));
+        let key = Self::property(field)?;
+        let id = format!("n.{}", pk);
+        let after = match order {
+            Order::Asc => ">",
+            Order::Desc => "<",
+        };
+
+        let mut query = self.clone();
+        query.order = vec![(field.to_string(), order)];
+        if field != pk {
+            query.order.push((pk.to_string(), order));
+        }
+        query.skip = None;
+        query.limit = Some(size + 1);
+
+        // Kuzu sorts nulls after every other value, so they come last in ascending
+        // order and first in descending order
+        if let Some(cursor) = cursor {
+            let (last_key, last_id) = decode_cursor::<N::Key>(cursor)?;
+            let mut condition = Fragment::default();
+            match (field == pk, last_key, order) {
+                (true, _, _) => {
+                    condition.push_str(&format!("{} {} ", id, after));
+                    condition.push_param(last_id);
+                },
+                (false, Some(last_key), _) => {
+                    condition.push_str(&format!("({} {} ", key, after));
+                    condition.push_param(last_key.clone());
+                    condition.push_str(&format!(" OR ({} = ", key));
+                    condition.push_param(last_key);
+                    condition.push_str(&format!(" AND {} {} ", id, after));
+                    condition.push_param(last_id);
+                    condition.push_str(")");
+                    if order == Order::Asc {
+                        condition.push_str(&format!(" OR {} IS NULL", key));
+                    }
+                    condition.push_str(")");
+                },
+                (false, None, Order::Asc) => {
+                    condition.push_str(&format!("({} IS NULL AND {} > ", key, id));
+                    condition.push_param(last_id);
+                    condition.push_str(")");
+                },
+                (false, None, Order::Desc) => {
+                    condition.push_str(&format!("({} IS NOT NULL OR {} < ", key, id));
+                    condition.push_param(last_id);
+                    condition.push_str(")");
+                },
+            }
+            query.conditions.push(condition);
+        }
+
+        // The sort key is returned as stored, since a node may read a null as a default
+        let (text, params) = query.render(&format!("n, {} AS sort_key", key), true).finish()?;
+        let mut rows = graph.query_as::<(N, Value)>(&text, params.as_vec())?;
+        let next_cursor = if rows.len() > size {
+            rows.truncate(size);
+            match rows.last() {
+                Some((last, sort_key)) => Some(encode_cursor(sort_key, &last.primary_key())?),
+                None => None,
+            }
+        } else {
+            None
+        };
+        let items = rows.into_iter().map(|(node, _)| node).collect();
+
+        Ok(Page { items, next_cursor })
+    }
+}
+
+/// A page of results returned by [`QueryBuilder::page_after`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page<N> {
+    /// The nodes in this page, in order
+    pub items: Vec<N>,
+    /// Cursor for the following page, or None if this is the last page
+    pub next_cursor: Option<String>,
+}
+
+/// A query projecting properties of the matching nodes, created by [`QueryBuilder::select`]
+#[derive(Debug)]
+pub struct Select<N, S> {
+    /// The query selecting the nodes
+    query: QueryBuilder<N>,
+    /// The projected properties, in order
+    fields: Vec<&'static str>,
+    /// Phantom data to hold the selection type
+    selection: PhantomData<S>,
+}
+
+impl<N: Node, S: Selection<N>> Select<N, S> {
+    /// Builds the Cypher query string
+    ///
+    /// Ordering by a projected property uses its column. With `distinct`, only
+    /// projected properties can be ordered by; other orders make `fetch` fail.
+    ///
+    /// # Returns
+    /// * A string containing the complete Cypher query
+    pub fn build(&self) -> String {
+        self.render().text()
+    }
+
+    fn render(&self) -> Fragment {
+        if self.query.distinct {
+            if let Some((field, _)) = self.query.order.iter().find(|(f, _)| !self.fields.contains(&f.as_str())) {
+                return Fragment::error(format!(
+                    "Cannot order distinct rows by {}, which is not selected", field
+                ));
+            }
+        }
+
+        let columns: Vec<String> = self.fields
+            .iter()
+            .map(|field| format!("n.{} AS {}", field, field))
+            .collect();
+        let order = if self.query.order.is_empty() {
+            String::new()
+        } else {
+            let order: Vec<String> = self.query.order
+                .iter()
+                .map(|(field, order)| if self.fields.contains(&field.as_str()) {
+                    format!("{} {}", field, order.as_str())
+                } else {
+                    format!("n.{} {}", field, order.as_str())
+                })
+                .collect();
+            format!(" ORDER BY {}", order.join(", "))
+        };
+        self.query.render_stage("RETURN", columns.join(", ").into(), &order)
+    }
+
+    /// Runs the query and returns one tuple of property values per row
+    ///
+    /// # Arguments
+    /// * `graph` - The graph to run the query against
+    ///
+    /// # Returns
+    /// * `Result<Vec<S::Output>>` - The projected rows or an error
+    pub fn fetch(&self, graph: &KuzuGraph) -> Result<Vec<S::Output>> {
+        let (query, params) = self.render().finish()?;
+        graph.query_as(&query, params.as_vec())
+    }
+}
+
+/// Encodes the sort key and primary key of a node as an opaque, URL-safe cursor
+fn encode_cursor<K: KeyType>(sort_key: &Value, key: &K) -> Result<String> {
+    let sort_key = value_to_json(sort_key).ok_or_else(|| Error::Query(
+        format!("Cannot store the sort key {:?} in a cursor", sort_key)
+    ))?;
+    let cursor = serde_json::json!([sort_key, key.to_key_string()]).to_string();
+    Ok(cursor.bytes().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Decodes a cursor produced by `encode_cursor` into its sort key, None if it
+/// was null, and the primary key as a value of the key column's type
+fn decode_cursor<K: KeyType>(cursor: &str) -> Result<(Option<Value>, Value)> {
+    let invalid = || Error::Query(format!("Invalid cursor: {}", cursor));
+
+    let bytes = (0..cursor.len())
+        .step_by(2)
+        .map(|i| cursor.get(i..i + 2).and_then(|b| u8::from_str_radix(b, 16).ok()))
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(invalid)?;
+    let key: serde_json::Value = serde_json::from_slice(&bytes).map_err(|_| invalid())?;
+
+    let Some([sort_key, serde_json::Value::String(id)]) = key.as_array().map(Vec::as_slice) else {
+        return Err(invalid());
+    };
+    let id = K::from_key_string(id).map_err(|_| invalid())?.to_value();
+    match sort_key {
+        serde_json::Value::Null => Ok((None, id)),
+        sort_key => json_to_value(sort_key).map(|sort_key| (Some(sort_key), id)).ok_or_else(invalid),
+    }
+}
+
+/// The union of several queries over the same node type, created by [`QueryBuilder::union`]
+#[derive(Debug, Clone)]
+pub struct UnionQuery<N> {
+    /// The first query
+    first: QueryBuilder<N>,
+    /// The queries combined with it, after `UNION` or `UNION ALL`
+    branches: Vec<(&'static str, QueryBuilder<N>)>,
+}
+
+impl<N: Node> UnionQuery<N> {
+    fn new(first: QueryBuilder<N>) -> Self {
+        Self { first, branches: Vec::new() }
+    }
+
+    /// Adds a query whose results are combined without duplicates
+    ///
+    /// # Returns
+    /// * Self for method chaining
+    #[must_use]
+    pub fn union(mut self, other: QueryBuilder<N>) -> Self {
+        self.branches.push(("UNION", other));
+        self
+    }
+
+    /// Adds a query whose results are combined with duplicates
+    ///
+    /// # Returns
+    /// * Self for method chaining
+    #[must_use]
+    pub fn union_all(mut self, other: QueryBuilder<N>) -> Self {
+        self.branches.push(("UNION ALL", other));
+        self
+    }
+
+    /// Builds the Cypher query string
+    ///
+    /// # Returns
+    /// * A string containing the complete Cypher query
+    pub fn build(&self) -> String {
+        self.render().text()
+    }
+
+    fn render(&self) -> Fragment {
+        let mut query = self.first.render("n", true);
+        for (keyword, branch) in &self.branches {
+            query.push_str(&format!(" {} ", keyword));
+            query.append(&branch.render("n", true));
+        }
+        query
+    }
+
+    /// Runs the queries and returns the nodes they match
+    ///
+    /// # Arguments
+    /// * `graph` - The graph to run the query against
+    ///
+    /// # Returns
+    /// * `Result<Vec<N>>` - The combined nodes or an error
+    pub fn fetch(&self, graph: &KuzuGraph) -> Result<Vec<N>>
+    where
+        N: FromValue,
+    {
+        let (query, params) = self.render().finish()?;
+        let rows = graph.query_as::<(N,)>(&query, params.as_vec())?;
+        Ok(rows.into_iter().map(|(node,)| node).collect())
+    }
+}
+
+/// A query whose aggregations are grouped by a property, created by [`QueryBuilder::group_by`]
+#[derive(Debug, Clone)]
+pub struct GroupedQuery<N, K> {
+    /// The query selecting the nodes to aggregate
+    query: QueryBuilder<N>,
+    /// The property to group by
+    field: String,
+    /// Phantom data to hold the key type
+    key_type: PhantomData<K>,
+}
+
+impl<N: Node, K: FromValue + Eq + Hash> GroupedQuery<N, K> {
+    /// Runs an aggregate expression once per group
+    fn aggregate<T: FromValue>(&self, graph: &KuzuGraph, expression: &str) -> Result<HashMap<K, T>> {
+        let key = QueryBuilder::<N>::property(&self.field)?;
+        let (query, params) = self.query.render_aggregate(&format!("{} AS key, {} AS value", key, expression)).finish()?;
+        Ok(graph.query_as::<(K, T)>(&query, params.as_vec())?.into_iter().collect())
+    }
+
+    /// Counts the matching nodes in each group
+    ///
+    /// # Returns
+    /// * `Result<HashMap<K, i64>>` - The count for each key or an error
+    pub fn count(&self, graph: &KuzuGraph) -> Result<HashMap<K, i64>> {
+        self.aggregate(graph, "COUNT(*)")
+    }
+
+    /// Sums a property within each group
+    ///
+    /// # Arguments
+    /// * `field` - The numeric property to sum
+    ///
+    /// # Returns
+    /// * `Result<HashMap<K, T>>` - The sum for each key or an error
+    pub fn sum<T: FromValue>(&self, graph: &KuzuGraph, field: &str) -> Result<HashMap<K, T>> {
+        self.aggregate(graph, &format!("SUM({})", QueryBuilder::<N>::property(field)?))
+    }
+
+    /// Averages a property within each group
+    ///
+    /// # Arguments
+    /// * `field` - The numeric property to average
+    ///
+    /// # Returns
+    /// * `Result<HashMap<K, f64>>` - The average for each key or an error
+    pub fn avg(&self, graph: &KuzuGraph, field: &str) -> Result<HashMap<K, f64>> {
+        self.aggregate(graph, &format!("AVG({})", QueryBuilder::<N>::property(field)?))
+    }
+
+    /// Finds the smallest value of a property within each group
+    ///
+    /// # Arguments
+    /// * `field` - The property to compare
+    ///
+    /// # Returns
+    /// * `Result<HashMap<K, T>>` - The minimum for each key or an error
+    pub fn min<T: FromValue>(&self, graph: &KuzuGraph, field: &str) -> Result<HashMap<K, T>> {
+        self.aggregate(graph, &format!("MIN({})", QueryBuilder::<N>::property(field)?))
+    }
+
+    /// Finds the largest value of a property within each group
+    ///
+    /// # Arguments
+    /// * `field` - The property to compare
+    ///
+    /// # Returns
+    /// * `Result<HashMap<K, T>>` - The maximum for each key or an error
+    pub fn max<T: FromValue>(&self, graph: &KuzuGraph, field: &str) -> Result<HashMap<K, T>> {
+        self.aggregate(graph, &format!("MAX({})", QueryBuilder::<N>::property(field)?))
+    }
+
+    /// Collects the values of a property within each group
+    ///
+    /// # Arguments
+    /// * `field` - The property to collect
+    ///
+    /// # Returns
+    /// * `Result<HashMap<K, Vec<T>>>` - The collected values for each key or an error
+    pub fn collect<T: FromValue>(&self, graph: &KuzuGraph, field: &str) -> Result<HashMap<K, Vec<T>>> {
+        self.aggregate(graph, &format!("COLLECT({})", QueryBuilder::<N>::property(field)?))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{Direction, Uuid};
+    use kuzu::LogicalType;
     use serde::{Serialize, Deserialize};
     use serde_json::json;
 
@@ -115,6 +1025,8 @@ mod tests {
             "TestNode"
         }
 
+        type Key = String;
+
         fn primary_key_field() -> &'static str {
             "id"
         }
@@ -131,6 +1043,46 @@ mod tests {
         }
     }
 
+    #[derive(Serialize, Deserialize)]
+    struct TestRel {
+        from: String,
+        to: String,
+    }
+
+    impl Relationship for TestRel {
+        fn label() -> &'static str {
+            "TEST_REL"
+        }
+
+        fn from(&self) -> String {
+            self.from.clone()
+        }
+
+        fn to(&self) -> String {
+            self.to.clone()
+        }
+
+        fn from_field() -> &'static str {
+            "from"
+        }
+
+        fn to_field() -> &'static str {
+            "to"
+        }
+
+        fn from_node_label() -> &'static str {
+            "TestNode"
+        }
+
+        fn to_node_label() -> &'static str {
+            "TestNode"
+        }
+
+        fn property_definitions() -> Vec<String> {
+            Vec::new()
+        }
+    }
+
     #[test]
     fn test_query_builder() {
         let query = QueryBuilder::<TestNode>::new()
@@ -144,4 +1096,149 @@ mod tests {
             "MATCH (n:TestNode) WHERE n.age > 18 RETURN n SKIP 5 LIMIT 10"
         );
     }
+
+    #[test]
+    fn test_query_builder_subqueries_and_unions() {
+        let friend = || Pattern::new()
+            .node::<TestNode>("n")
+            .rel::<TestRel>("", Direction::Outgoing)
+            .node::<TestNode>("m");
+
+        let query = QueryBuilder::<TestNode>::new()
+            .where_clause("n.name <> '$p0'")
+            .exists(friend().filter("m", "name", Cmp::Eq, "a"))
+            .count_matches(friend(), Cmp::Lt, 3);
+        assert_eq!(
+            query.build(),
+            "MATCH (n:TestNode) WHERE n.name <> '$p0' \
+             AND EXISTS { MATCH (n:TestNode)-[:TEST_REL]->(m:TestNode) WHERE m.name = $p0 } \
+             AND COUNT { MATCH (n:TestNode)-[:TEST_REL]->(m:TestNode) } < $p1 RETURN n"
+        );
+
+        let by_name = |name: &str| {
+            let mut query = QueryBuilder::<TestNode>::new();
+            let mut condition = Fragment::from("n.name = ");
+            condition.push_param(Value::String(name.to_string()));
+            query.conditions.push(condition);
+            query
+        };
+        let union = by_name("a").union(by_name("b")).union_all(QueryBuilder::new().limit(1));
+        assert_eq!(
+            union.build(),
+            "MATCH (n:TestNode) WHERE n.name = $p0 RETURN n \
+             UNION MATCH (n:TestNode) WHERE n.name = $p1 RETURN n \
+             UNION ALL MATCH (n:TestNode) RETURN n LIMIT 1"
+        );
+        assert_eq!(union.render().finish().unwrap().1.as_vec().len(), 2);
+
+        let staged = by_name("a")
+            .order_by("name", Order::Asc)
+            .limit(5)
+            .project(&["n"])
+            .node::<TestNode>("n")
+            .rel::<TestRel>("", Direction::Outgoing)
+            .node::<TestNode>("m")
+            .returns(&["n", "m"])
+            .build()
+            .unwrap();
+        assert_eq!(
+            staged,
+            "MATCH (n:TestNode) WHERE n.name = $p0 WITH n ORDER BY n.name ASC LIMIT 5 \
+             MATCH (n:TestNode)-[:TEST_REL]->(m:TestNode) RETURN n, m"
+        );
+
+        // Invalid subpatterns fail when the query runs
+        let invalid = QueryBuilder::<TestNode>::new().exists(Pattern::new().rel::<TestRel>("r", Direction::Outgoing));
+        assert!(invalid.render("n", true).finish().is_err());
+    }
+
+    #[test]
+    fn test_query_builder_aggregates() {
+        let query = QueryBuilder::<TestNode>::new().where_clause("n.age > 18");
+
+        assert_eq!(
+            query.render(&format!("COUNT({}) AS value", QueryBuilder::<TestNode>::property("name").unwrap()), false).text(),
+            "MATCH (n:TestNode) WHERE n.age > 18 RETURN COUNT(n.name) AS value"
+        );
+        assert_eq!(
+            query.clone().order_by("name", Order::Asc).skip(1).limit(2).render_aggregate("COUNT(*) AS value").text(),
+            "MATCH (n:TestNode) WHERE n.age > 18 WITH n ORDER BY n.name ASC SKIP 1 LIMIT 2 \
+             RETURN COUNT(*) AS value"
+        );
+        assert!(matches!(
+            QueryBuilder::<TestNode>::property("age"),
+            Err(Error::Query(_))
+        ));
+    }
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = encode_cursor(&Value::Int64(42), &"n1".to_string()).unwrap();
+        let (key, id) = decode_cursor::<String>(&cursor).unwrap();
+        assert!(matches!(key, Some(Value::Int64(42))));
+        assert!(matches!(id, Value::String(ref s) if s == "n1"));
+
+        // Null sort keys and typed primary keys survive the round trip
+        let uuid = Uuid::from_u128(0x1234_5678);
+        let cursor = encode_cursor(&Value::Null(LogicalType::Int64), &uuid).unwrap();
+        let (key, id) = decode_cursor::<Uuid>(&cursor).unwrap();
+        assert!(key.is_none());
+        assert!(matches!(id, Value::UUID(u) if u == uuid));
+
+        assert!(decode_cursor::<String>("not a cursor").is_err());
+        assert!(decode_cursor::<i64>(&cursor).is_err());
+    }
+
+    #[test]
+    fn test_eager_query() {
+        let query = QueryBuilder::<TestNode>::new()
+            .where_clause("n.name = 'a'")
+            .order_by("name", Order::Asc)
+            .limit(5)
+            .with::<TestRel, TestNode>()
+            .build();
+
+        assert_eq!(
+            query,
+            "MATCH (n:TestNode) WHERE n.name = 'a' WITH n ORDER BY n.name ASC LIMIT 5 \
+             OPTIONAL MATCH (n)-[r:TEST_REL]->(m:TestNode) \
+             RETURN n, COLLECT({r: r, m: m}) ORDER BY n.name ASC"
+        );
+    }
+
+    #[test]
+    fn test_optional_query() {
+        let query = QueryBuilder::<TestNode>::new()
+            .optional::<TestRel, TestNode>()
+            .direction(Direction::Incoming)
+            .where_clause("m.name <> 'b'")
+            .build();
+
+        assert_eq!(
+            query,
+            "MATCH (n:TestNode) WITH n OPTIONAL MATCH (n)<-[r:TEST_REL]-(m:TestNode) \
+             WHERE m.name <> 'b' RETURN n, r, m"
+        );
+    }
+
+    #[test]
+    fn test_order_distinct_and_select() {
+        let query = QueryBuilder::<TestNode>::new()
+            .order_by("name", Order::Desc)
+            .order_by("id", Order::Asc)
+            .distinct()
+            .limit(3);
+        assert_eq!(
+            query.build(),
+            "MATCH (n:TestNode) RETURN DISTINCT n ORDER BY n.name DESC, n.id ASC LIMIT 3"
+        );
+
+        let name = Field::<TestNode, String>::new("name");
+        let id = Field::<TestNode, String>::new("id");
+        assert_eq!(
+            query.clone().select((name, id)).build(),
+            "MATCH (n:TestNode) RETURN DISTINCT n.name AS name, n.id AS id ORDER BY name DESC, id ASC LIMIT 3"
+        );
+        assert!(query.select((name,)).render().finish().is_err());
+    }
 }
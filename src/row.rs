@@ -0,0 +1,350 @@
+use crate::{Result, Error, Node, Relationship, KuzuGraph};
+use kuzu::{NodeVal, RelVal, Value};
+use serde_json::json;
+use std::collections::HashMap;
+
+/// Primary key fields of the node labels a result type may contain
+///
+/// Used to fill in the `from`/`to` fields of relationships from the endpoint
+/// nodes returned alongside them.
+#[derive(Debug, Default, Clone)]
+pub struct PrimaryKeys {
+    fields: HashMap<String, String>,
+}
+
+impl PrimaryKeys {
+    /// Registers the primary key field of a node label
+    pub fn insert<L: Into<String>, F: Into<String>>(&mut self, label: L, field: F) {
+        self.fields.insert(label.into(), field.into());
+    }
+
+    /// Registers the primary key field of a node type
+    pub fn register<N: Node>(&mut self) {
+        self.insert(N::label(), N::primary_key_field());
+    }
+
+    /// Registers every primary key field known to another set
+    pub(crate) fn extend(&mut self, other: &PrimaryKeys) {
+        self.fields.extend(other.fields.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+
+    fn get(&self, label: &str) -> Option<&str> {
+        self.fields.get(label).map(String::as_str)
+    }
+}
+
+/// A single row of a query result, with its columns addressable by alias
+pub struct Row<'a> {
+    columns: &'a [String],
+    values: Vec<Value>,
+    keys: &'a PrimaryKeys,
+    nodes: HashMap<(u64, u64), NodeVal>,
+}
+
+impl<'a> Row<'a> {
+    /// Creates a row from the column names and values of a query result
+    pub fn new(columns: &'a [String], values: Vec<Value>, keys: &'a PrimaryKeys) -> Self {
+        let mut nodes = HashMap::new();
+        for value in &values {
+            collect_nodes(value, &mut nodes);
+        }
+        Self { columns, values, keys, nodes }
+    }
+
+    /// Gets the raw value at a column index
+    pub fn value(&self, index: usize) -> Result<&Value> {
+        self.values
+            .get(index)
+            .ok_or_else(|| Error::Query(format!("Column {} not found in result", index)))
+    }
+
+    /// Gets a column by position and converts it to `T`
+    pub fn get_index<T: FromValue>(&self, index: usize) -> Result<T> {
+        T::from_value(self.value(index)?, self)
+    }
+
+    /// Gets a column by alias and converts it to `T`
+    pub fn get<T: FromValue>(&self, column: &str) -> Result<T> {
+        let index = self.columns
+            .iter()
+            .position(|c| c == column)
+            .ok_or_else(|| Error::Query(format!("Column {} not found in result", column)))?;
+        self.get_index(index)
+    }
+
+    /// Looks up the primary key of a node returned elsewhere in this row
+    fn node_key(&self, id: &kuzu::InternalID) -> Option<serde_json::Value> {
+        let node = self.nodes.get(&(id.table_id, id.offset))?;
+        let field = self.keys.get(node.get_label_name())?;
+        node.get_properties()
+            .iter()
+            .find(|(key, _)| key.as_str() == field)
+            .and_then(|(_, value)| value_to_json(value))
+    }
+}
+
+/// Conversion from a single query result value
+///
+/// Implemented for primitives, `Option`, `Vec`, and by the `Node` and
+/// `Relationship` derives.
+pub trait FromValue: Sized {
+    /// Converts a value of the given row into `Self`
+    fn from_value(value: &Value, row: &Row) -> Result<Self>;
+
+    /// Registers the node types this value may contain
+    fn register_keys(_keys: &mut PrimaryKeys) {}
+}
+
+/// Conversion from a whole query result row
+///
+/// Derive it with `#[derive(FromRow)]` to map columns onto struct fields by alias,
+/// or use a tuple to map columns by position.
+pub trait FromRow: Sized {
+    /// Converts a row into `Self`
+    fn from_row(row: &Row) -> Result<Self>;
+
+    /// Registers the node types this row may contain
+    fn register_keys(_keys: &mut PrimaryKeys) {}
+}
+
+impl KuzuGraph {
+    /// Execute a parameterized Cypher query and map every row onto `T`
+    ///
+    /// # Arguments
+    /// * `query` - The Cypher query, with `$name` parameters
+    /// * `params` - Values for the query parameters
+    ///
+    /// # Returns
+    /// * `Result<Vec<T>>` - The mapped rows or an error
+    pub fn query_as<T: FromRow>(&self, query: &str, params: Vec<(&str, Value)>) -> Result<Vec<T>> {
+        self.query_as_with_keys(query, params, PrimaryKeys::default())
+    }
+
+    /// Like [`KuzuGraph::query_as`], with extra primary keys for nodes that are
+    /// returned in the row but not part of `T`
+    pub(crate) fn query_as_with_keys<T: FromRow>(
+        &self,
+        query: &str,
+        params: Vec<(&str, Value)>,
+        mut keys: PrimaryKeys,
+    ) -> Result<Vec<T>> {
+        let conn = self.get_connection()?;
+        let mut statement = conn.prepare(query)?;
+        let result = conn.execute(&mut statement, params)?;
+        let columns = result.get_column_names();
+
+        T::register_keys(&mut keys);
+
+        result
+            .map(|values| T::from_row(&Row::new(&columns, values, &keys)))
+            .collect()
+    }
+}
+
+/// Converts a kuzu value into JSON, returning `None` for types with no JSON equivalent
+pub(crate) fn value_to_json(value: &Value) -> Option<serde_json::Value> {
+    Some(match value {
+        Value::Null(_) => serde_json::Value::Null,
+        Value::String(s) => json!(s),
+        Value::Bool(b) => json!(b),
+        Value::Int64(n) => json!(n),
+        Value::Int32(n) => json!(n),
+        Value::Int16(n) => json!(n),
+        Value::Int8(n) => json!(n),
+        Value::UInt64(n) => json!(n),
+        Value::UInt32(n) => json!(n),
+        Value::UInt16(n) => json!(n),
+        Value::UInt8(n) => json!(n),
+        Value::Double(n) => json!(n),
+        Value::Float(n) => json!(n),
+        Value::UUID(u) => json!(u.to_string()),
+        Value::List(_, items) | Value::Array(_, items) => serde_json::Value::Array(
+            items.iter().map(|v| value_to_json(v).unwrap_or(serde_json::Value::Null)).collect()
+        ),
+        Value::Struct(fields) => properties_to_json(fields),
+        Value::Node(node) => node_to_json(node),
+        Value::Rel(rel) => properties_to_json(rel.get_properties()),
+        _ => return None, // Skip unsupported types
+    })
+}
+
+/// Converts a JSON property into a kuzu value, returning `None` for unsupported types
+pub(crate) fn json_to_value(value: &serde_json::Value) -> Option<Value> {
+    match value {
+        serde_json::Value::String(s) => Some(Value::String(s.clone())),
+        serde_json::Value::Bool(b) => Some(Value::Bool(*b)),
+        serde_json::Value::Number(n) => {
+            if let Some(n) = n.as_i64() {
+                Some(Value::Int64(n))
+            } else {
+                n.as_f64().map(Value::Double)
+            }
+        },
+        _ => None, // Skip unsupported types
+    }
+}
+
+/// Converts the properties of a node into a JSON object
+pub(crate) fn node_to_json(node: &NodeVal) -> serde_json::Value {
+    properties_to_json(node.get_properties())
+}
+
+fn properties_to_json(properties: &[(String, Value)]) -> serde_json::Value {
+    let mut props = serde_json::Map::new();
+    for (key, value) in properties {
+        if let Some(value) = value_to_json(value) {
+            props.insert(key.clone(), value);
+        }
+    }
+    serde_json::Value::Object(props)
+}
+
+fn collect_nodes(value: &Value, nodes: &mut HashMap<(u64, u64), NodeVal>) {
+    match value {
+        Value::Node(node) => {
+            let id = node.get_node_id();
+            nodes.insert((id.table_id, id.offset), node.clone());
+        },
+        Value::List(_, items) | Value::Array(_, items) => {
+            for item in items {
+                collect_nodes(item, nodes);
+            }
+        },
+        Value::Struct(fields) => {
+            for (_, field) in fields {
+                collect_nodes(field, nodes);
+            }
+        },
+        Value::RecursiveRel { nodes: path_nodes, .. } => {
+            for node in path_nodes {
+                let id = node.get_node_id();
+                nodes.insert((id.table_id, id.offset), node.clone());
+            }
+        },
+        _ => {}
+    }
+}
+
+/// Deserializes node properties into a node type and binds its relation fields
+pub(crate) fn node_from_json<N: Node>(mut properties: serde_json::Value) -> Result<N> {
+    // Columns added after the node was written, or left null, fall back to their defaults
+    if let serde_json::Value::Object(obj) = &mut properties {
+        for (key, default) in N::default_properties() {
+            if obj.get(&key).is_none_or(serde_json::Value::is_null) {
+                obj.insert(key, default);
+            }
+        }
+    }
+    let mut node: N = serde_json::from_value(properties)?;
+    node.bind_relations();
+    Ok(node)
+}
+
+/// Deserializes a node value into a node type
+pub fn node_from_value<N: Node>(value: &Value) -> Result<N> {
+    match value {
+        Value::Node(node) => node_from_json(node_to_json(node)),
+        other => Err(Error::InvalidPropertyType(
+            format!("Expected a {} node, got {}", N::label(), other)
+        )),
+    }
+}
+
+/// Deserializes a relationship value into a relationship type
+///
+/// The `#[from_node]` and `#[to_node]` fields are filled with the primary keys of
+/// the endpoint nodes, which must be returned in the same row.
+pub fn relationship_from_value<R: Relationship>(value: &Value, row: &Row) -> Result<R> {
+    match value {
+        Value::Rel(rel) => Ok(serde_json::from_value(rel_to_json::<R>(rel, row)?)?),
+        other => Err(Error::InvalidPropertyType(
+            format!("Expected a {} relationship, got {}", R::label(), other)
+        )),
+    }
+}
+
+fn rel_to_json<R: Relationship>(rel: &RelVal, row: &Row) -> Result<serde_json::Value> {
+    let mut props = properties_to_json(rel.get_properties());
+    for (field, node) in [(R::from_field(), rel.get_src_node()), (R::to_field(), rel.get_dst_node())] {
+        let key = row.node_key(node).ok_or_else(|| Error::Query(format!(
+            "Cannot read {}.{}: the endpoint node must be returned in the same row",
+            R::label(), field
+        )))?;
+        props[field] = key;
+    }
+    Ok(props)
+}
+
+macro_rules! impl_from_value_json {
+    ($($ty:ty),*) => {
+        $(
+            impl FromValue for $ty {
+                fn from_value(value: &Value, _row: &Row) -> Result<Self> {
+                    let json = value_to_json(value).ok_or_else(|| Error::InvalidPropertyType(
+                        format!("Cannot convert {} to {}", value, stringify!($ty))
+                    ))?;
+                    Ok(serde_json::from_value(json)?)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_value_json!(
+    String, bool, i8, i16, i32, i64, u8, u16, u32, u64, f32, f64, uuid::Uuid, serde_json::Value
+);
+
+impl FromValue for Value {
+    fn from_value(value: &Value, _row: &Row) -> Result<Self> {
+        Ok(value.clone())
+    }
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value(value: &Value, row: &Row) -> Result<Self> {
+        match value {
+            Value::Null(_) => Ok(None),
+            other => T::from_value(other, row).map(Some),
+        }
+    }
+
+    fn register_keys(keys: &mut PrimaryKeys) {
+        T::register_keys(keys);
+    }
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+    fn from_value(value: &Value, row: &Row) -> Result<Self> {
+        match value {
+            Value::List(_, items) | Value::Array(_, items) => {
+                items.iter().map(|item| T::from_value(item, row)).collect()
+            },
+            other => Err(Error::InvalidPropertyType(format!("Expected a list, got {}", other))),
+        }
+    }
+
+    fn register_keys(keys: &mut PrimaryKeys) {
+        T::register_keys(keys);
+    }
+}
+
+macro_rules! impl_from_row_tuple {
+    ($($ty:ident => $idx:tt),+) => {
+        impl<$($ty: FromValue),+> FromRow for ($($ty,)+) {
+            fn from_row(row: &Row) -> Result<Self> {
+                Ok(($(row.get_index::<$ty>($idx)?,)+))
+            }
+
+            fn register_keys(keys: &mut PrimaryKeys) {
+                $($ty::register_keys(keys);)+
+            }
+        }
+    };
+}
+
+impl_from_row_tuple!(A => 0);
+impl_from_row_tuple!(A => 0, B => 1);
+impl_from_row_tuple!(A => 0, B => 1, C => 2);
+impl_from_row_tuple!(A => 0, B => 1, C => 2, D => 3);
+impl_from_row_tuple!(A => 0, B => 1, C => 2, D => 3, E => 4);
+impl_from_row_tuple!(A => 0, B => 1, C => 2, D => 3, E => 4, F => 5);
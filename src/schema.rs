@@ -0,0 +1,297 @@
+use crate::{Result, Error, Node, KuzuGraph, KeyType};
+use crate::row::json_to_value;
+use kuzu::{Connection, Value};
+
+/// Node table recording the indexes and constraints OkoData enforces itself
+const INDEX_TABLE: &str = "OkodataIndex";
+
+/// Kind of index created by [`KuzuGraph::create_index`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IndexKind {
+    /// A uniqueness constraint, which Kuzu only enforces on primary keys;
+    /// OkoData checks it when nodes are written
+    Unique,
+}
+
+impl IndexKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            IndexKind::Unique => "unique",
+        }
+    }
+}
+
+/// An index or constraint defined on a node table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexInfo {
+    /// Name of the index
+    pub name: String,
+    /// Label of the indexed table
+    pub label: String,
+    /// Indexed properties
+    pub fields: Vec<String>,
+    /// Kind of index
+    pub kind: IndexKind,
+}
+
+/// Column definition of a property, with its `#[property(default = ..)]` clause
+pub(crate) fn property_column<N: Node>(name: &str, col_type: &str) -> String {
+    match N::column_defaults().into_iter().find(|(field, _)| *field == name) {
+        Some((_, default)) => format!("{} {} DEFAULT CAST({} AS {})", name, col_type, default, col_type),
+        None => format!("{} {}", name, col_type),
+    }
+}
+
+/// Quotes a string as a Cypher literal
+pub(crate) fn quote(text: &str) -> String {
+    format!("'{}'", text.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// Name of the index of the given kind on a single property
+fn index_name(label: &str, field: &str, kind: IndexKind) -> String {
+    format!("{}_{}_{}", label, field, kind.as_str())
+}
+
+impl KuzuGraph {
+    /// Creates the table of a node type together with the indexes declared on it
+    ///
+    /// Safe to call again on an existing database; tables and indexes that already
+    /// exist are left as they are.
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or an error
+    pub fn init_schema<N: Node>(&self) -> Result<()> {
+        self.create_node_table::<N>()
+    }
+
+    /// Adds the columns of a node table that do not exist yet, with their defaults
+    ///
+    /// Existing nodes get the column's `DEFAULT` value, or null if it has none.
+    ///
+    /// # Arguments
+    /// * `label` - The node table to alter
+    /// * `columns` - Column definitions of the form `name TYPE [DEFAULT ..]`
+    pub(crate) fn add_missing_columns(&self, label: &str, columns: &[String]) -> Result<()> {
+        let existing: Vec<String> = self
+            .query(&format!("CALL table_info('{}') RETURN name", label))?
+            .filter_map(|row| match row.first() {
+                Some(Value::String(name)) => Some(name.clone()),
+                _ => None,
+            })
+            .collect();
+
+        for column in columns {
+            let name = column.split_whitespace().next().unwrap_or_default();
+            if !existing.iter().any(|existing| existing == name) {
+                self.query(&format!("ALTER TABLE {} ADD {}", label, column))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates an index on a property of a node type, if it does not exist yet
+    ///
+    /// # Arguments
+    /// * `field` - The property to index
+    /// * `kind` - The kind of index to create
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or an error if the property is unknown or, for a
+    ///   unique index, already holds duplicate values
+    pub fn create_index<N: Node>(&self, field: &str, kind: IndexKind) -> Result<()> {
+        let label = N::label();
+        let name = index_name(label, field, kind);
+        let known = matches!(N::property_types(), serde_json::Value::Object(obj) if obj.contains_key(field));
+        if !known {
+            return Err(Error::Schema(format!("{} has no property {}", label, field)));
+        }
+
+        match kind {
+            IndexKind::Unique => {
+                let duplicates = self.query(&format!(
+                    "MATCH (n:{}) WHERE n.{} IS NOT NULL WITH n.{} AS value, COUNT(*) AS count \
+                     WHERE count > 1 RETURN value LIMIT 1",
+                    label, field, field
+                ))?;
+                if let Some(row) = duplicates.into_iter().next() {
+                    return Err(Error::Schema(format!(
+                        "Cannot create unique index {}: duplicate value {}", name, row[0]
+                    )));
+                }
+
+                self.query(&format!(
+                    "CREATE NODE TABLE IF NOT EXISTS {} \
+                     (name STRING, label STRING, field STRING, kind STRING, PRIMARY KEY(name))",
+                    INDEX_TABLE
+                ))?;
+                *self.index_table.lock().unwrap() = Some(true);
+                self.query(&format!(
+                    "MERGE (i:{} {{name: {}}}) SET i.label = {}, i.field = {}, i.kind = {}",
+                    INDEX_TABLE, quote(&name), quote(label), quote(field), quote(kind.as_str())
+                ))?;
+            },
+        }
+        Ok(())
+    }
+
+    /// Drops an index created by [`KuzuGraph::create_index`] or a derive attribute
+    ///
+    /// # Arguments
+    /// * `field` - The indexed property
+    /// * `kind` - The kind of index to drop
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or an error if the index does not exist
+    pub fn drop_index<N: Node>(&self, field: &str, kind: IndexKind) -> Result<()> {
+        let label = N::label();
+        let name = index_name(label, field, kind);
+        if !self.list_indexes::<N>()?.iter().any(|index| index.name == name) {
+            return Err(Error::Schema(format!("No index {} on {}", name, label)));
+        }
+
+        match kind {
+            IndexKind::Unique => {
+                self.query(&format!("MATCH (i:{} {{name: {}}}) DELETE i", INDEX_TABLE, quote(&name)))?;
+            },
+        }
+        Ok(())
+    }
+
+    /// Lists the indexes and constraints defined on a node type
+    ///
+    /// These are the unique constraints OkoData enforces, whether declared with
+    /// `#[unique]` or created with [`KuzuGraph::create_index`]. The primary key
+    /// index is not listed.
+    ///
+    /// # Returns
+    /// * `Result<Vec<IndexInfo>>` - The indexes or an error
+    pub fn list_indexes<N: Node>(&self) -> Result<Vec<IndexInfo>> {
+        let label = N::label();
+        let mut indexes = Vec::new();
+        let conn = self.get_connection()?;
+        for field in self.registered_unique_fields(&conn, label)? {
+            indexes.push(IndexInfo {
+                name: index_name(label, &field, IndexKind::Unique),
+                label: label.to_string(),
+                fields: vec![field],
+                kind: IndexKind::Unique,
+            });
+        }
+        Ok(indexes)
+    }
+
+    /// Creates the indexes declared on a node type with `#[unique]`
+    pub(crate) fn create_declared_indexes<N: Node>(&self) -> Result<()> {
+        for (field, kind) in N::indexes() {
+            self.create_index::<N>(field, kind)?;
+        }
+        Ok(())
+    }
+
+    /// Whether the registry table of unique constraints exists
+    ///
+    /// Looked up once per graph; [`KuzuGraph::create_index`] marks it as created.
+    fn has_index_table(&self, conn: &Connection) -> Result<bool> {
+        let mut cached = self.index_table.lock().unwrap();
+        if let Some(exists) = *cached {
+            return Ok(exists);
+        }
+        let tables = conn.query("CALL SHOW_TABLES() RETURN name")?;
+        let exists = tables.into_iter().any(|row| matches!(&row[0], Value::String(name) if name == INDEX_TABLE));
+        *cached = Some(exists);
+        Ok(exists)
+    }
+
+    /// Properties of a label with a unique constraint registered through `create_index`
+    fn registered_unique_fields(&self, conn: &Connection, label: &str) -> Result<Vec<String>> {
+        if !self.has_index_table(conn)? {
+            return Ok(Vec::new());
+        }
+
+        let mut statement = conn.prepare(&format!(
+            "MATCH (i:{}) WHERE i.label = $label AND i.kind = $kind RETURN i.field",
+            INDEX_TABLE
+        ))?;
+        let result = conn.execute(&mut statement, vec![
+            ("label", Value::String(label.to_string())),
+            ("kind", Value::String(IndexKind::Unique.as_str().to_string())),
+        ])?;
+        Ok(result
+            .filter_map(|row| match row.into_iter().next() {
+                Some(Value::String(field)) => Some(field),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// Properties of `N` whose values must be unique, declared or registered
+    ///
+    /// The registry is only queried once a unique index exists on the graph, so
+    /// writes to node types without unique properties cost no extra round-trip.
+    fn unique_fields<N: Node>(&self, conn: &Connection) -> Result<Vec<String>> {
+        let mut fields = self.registered_unique_fields(conn, N::label())?;
+        for (field, kind) in N::indexes() {
+            if kind == IndexKind::Unique && !fields.iter().any(|f| f == field) {
+                fields.push(field.to_string());
+            }
+        }
+        Ok(fields)
+    }
+
+    /// Checks that no other node of type `N` has the same value for a unique property
+    ///
+    /// Runs on the given connection, so that inside a transaction the check and
+    /// the write that follows it see the same data. The node itself is excluded
+    /// by its key, except for new nodes whose key the database generates.
+    pub(crate) fn check_unique<N: Node>(&self, conn: &Connection, node: &N, creating: bool) -> Result<()> {
+        let properties = node.properties();
+        let own_key = !(creating && N::key_generation().is_some());
+        for field in self.unique_fields::<N>(conn)? {
+            let Some(value) = properties.get(&field).and_then(json_to_value) else {
+                continue;
+            };
+            let mut query = format!("MATCH (n:{}) WHERE n.{} = $value", N::label(), field);
+            let mut params = vec![("value", value)];
+            if own_key {
+                query.push_str(&format!(" AND n.{} <> $id", N::primary_key_field()));
+                params.push(("id", node.primary_key().to_value()));
+            }
+            query.push_str(" RETURN COUNT(*)");
+
+            let mut statement = conn.prepare(&query)?;
+            let result = conn.execute(&mut statement, params)?;
+            let taken = result
+                .into_iter()
+                .next()
+                .is_some_and(|row| matches!(row.first(), Some(Value::Int64(count)) if *count > 0));
+
+            if taken {
+                return Err(Error::ConstraintViolation {
+                    label: N::label().to_string(),
+                    value: match &properties[&field] {
+                        serde_json::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    },
+                    field,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("it's"), "'it\\'s'");
+        assert_eq!(quote("a\\b"), "'a\\\\b'");
+    }
+
+    #[test]
+    fn test_index_kind_names() {
+        assert_eq!(index_name("User", "email", IndexKind::Unique), "User_email_unique");
+    }
+}
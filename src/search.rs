@@ -0,0 +1,116 @@
+use crate::{Result, Error, Node, KuzuGraph, FromValue};
+use kuzu::Value;
+use std::collections::HashMap;
+
+/// BM25 term frequency saturation
+const K1: f64 = 1.2;
+
+/// Splits text into lowercase words
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// BM25 weight of a term occurring `tf` times in a node, and in `df` of `total` nodes
+///
+/// Field lengths are not taken into account (BM25 with `b = 0`).
+fn bm25(tf: usize, df: usize, total: usize) -> f64 {
+    let (tf, df, total) = (tf as f64, df as f64, total as f64);
+    let idf = (1.0 + (total - df + 0.5) / (df + 0.5)).ln();
+    idf * tf * (K1 + 1.0) / (tf + K1)
+}
+
+impl KuzuGraph {
+    /// Searches the `#[fulltext]` fields of a node type for keywords
+    ///
+    /// Kuzu 0.7.1 has no full-text index, so this is a scan: the database returns
+    /// every node whose fields contain one of the keywords, and the matches are ranked
+    /// on the client with BM25. Keywords match whole words, ignoring case, without
+    /// stemming. The cost grows with the number of nodes of type `N`.
+    ///
+    /// # Arguments
+    /// * `text` - The keywords to search for
+    /// * `limit` - Maximum number of results to return
+    ///
+    /// # Returns
+    /// * `Result<Vec<(N, f64)>>` - Matching nodes with their scores, best first
+    pub fn search<N: Node + FromValue>(&self, text: &str, limit: usize) -> Result<Vec<(N, f64)>> {
+        let fields = N::fulltext_fields();
+        if fields.is_empty() {
+            return Err(Error::Query(format!("{} has no #[fulltext] fields", N::label())));
+        }
+        let mut terms: Vec<String> = words(text).collect();
+        terms.sort();
+        terms.dedup();
+        if terms.is_empty() || limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let names: Vec<String> = (0..terms.len()).map(|i| format!("t{}", i)).collect();
+        let conditions: Vec<String> = names
+            .iter()
+            .flat_map(|name| fields.iter().map(move |field| format!("lower(n.{}) CONTAINS ${}", field, name)))
+            .collect();
+        let query = format!("MATCH (n:{}) WHERE {} RETURN n", N::label(), conditions.join(" OR "));
+        let params = names
+            .iter()
+            .zip(&terms)
+            .map(|(name, term)| (name.as_str(), Value::String(term.clone())))
+            .collect();
+        let candidates = self.query_as::<(N,)>(&query, params)?;
+
+        // Count the whole words of each candidate, since CONTAINS also matches inside words
+        let mut matches: Vec<(N, HashMap<String, usize>)> = Vec::new();
+        for (node,) in candidates {
+            let properties = node.properties();
+            let mut counts = HashMap::new();
+            for field in &fields {
+                let text = properties[*field].as_str().unwrap_or_default();
+                for word in words(text).filter(|word| terms.contains(word)) {
+                    *counts.entry(word).or_insert(0) += 1;
+                }
+            }
+            if !counts.is_empty() {
+                matches.push((node, counts));
+            }
+        }
+
+        let total = self
+            .query_as::<(i64,)>(&format!("MATCH (n:{}) RETURN COUNT(*)", N::label()), Vec::new())?
+            .first()
+            .map_or(0, |(count,)| *count as usize);
+        let df = |term: &String| matches.iter().filter(|(_, counts)| counts.contains_key(term)).count();
+        let df: HashMap<&String, usize> = terms.iter().map(|term| (term, df(term))).collect();
+
+        let mut results: Vec<(N, f64)> = matches
+            .into_iter()
+            .map(|(node, counts)| {
+                let score = counts.iter().map(|(term, tf)| bm25(*tf, df[term], total)).sum();
+                (node, score)
+            })
+            .collect();
+        results.sort_by(|a, b| b.1.total_cmp(&a.1));
+        results.truncate(limit);
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_words() {
+        let found: Vec<String> = words("Graph-databases, in Rust!").collect();
+        assert_eq!(found, vec!["graph", "databases", "in", "rust"]);
+    }
+
+    #[test]
+    fn test_bm25() {
+        // Rarer terms and repeated terms score higher, with diminishing returns
+        assert!(bm25(1, 1, 10) > bm25(1, 5, 10));
+        assert!(bm25(2, 1, 10) > bm25(1, 1, 10));
+        assert!(bm25(2, 1, 10) < 2.0 * bm25(1, 1, 10));
+    }
+}
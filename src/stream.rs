@@ -0,0 +1,49 @@
+use crate::{Result, Error, Node, KuzuGraph};
+use crate::row::node_from_value;
+use kuzu::{Connection, QueryResult};
+use std::marker::PhantomData;
+
+/// Iterator that deserializes the nodes of a query result one row at a time
+///
+/// Holds the connection the query ran on, so the result stays valid for
+/// as long as the iterator is alive.
+pub struct NodeStream<'g, N> {
+    // Declared before the connection so it is dropped first
+    result: QueryResult,
+    _connection: Connection<'g>,
+    node_type: PhantomData<N>,
+}
+
+impl<N: Node> Iterator for NodeStream<'_, N> {
+    type Item = Result<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let row = self.result.next()?;
+        Some(match row.first() {
+            Some(value) => node_from_value(value),
+            None => Err(Error::Query("Query returned no columns".into())),
+        })
+    }
+}
+
+impl KuzuGraph {
+    /// Execute a raw Cypher query and lazily deserialize the node in its first column
+    ///
+    /// Rows are only read from the result as the iterator is advanced, so large scans
+    /// never hold more than one node in memory.
+    ///
+    /// # Arguments
+    /// * `query` - A Cypher query returning nodes of type `N`, e.g. `MATCH (n:User) RETURN n`
+    ///
+    /// # Returns
+    /// * `Result<NodeStream<N>>` - An iterator over the deserialized nodes or an error
+    pub fn stream<N: Node>(&self, query: &str) -> Result<NodeStream<'_, N>> {
+        let connection = self.get_connection()?;
+        let result = connection.query(query)?;
+        Ok(NodeStream {
+            result,
+            _connection: connection,
+            node_type: PhantomData,
+        })
+    }
+}
@@ -1,15 +1,25 @@
+use crate::{IndexKind, KeyType, KeyGeneration, ValidationErrors};
+use kuzu::Value;
 use serde::{Serialize, Deserialize};
 
 /// Trait for node types in the graph
 pub trait Node: Serialize + for<'de> Deserialize<'de> {
     /// Get the label for this node type
     fn label() -> &'static str;
+
+    /// The type of the primary key; a tuple for composite keys
+    type Key: KeyType;
     
     /// Get the primary key field name
     fn primary_key_field() -> &'static str;
     
     /// Get the primary key value
-    fn primary_key(&self) -> String;
+    fn primary_key(&self) -> Self::Key;
+
+    /// How the database generates the primary key, if it does
+    fn key_generation() -> Option<KeyGeneration> {
+        None
+    }
     
     /// Get the properties as a JSON value
     fn properties(&self) -> serde_json::Value {
@@ -18,6 +28,42 @@ pub trait Node: Serialize + for<'de> Deserialize<'de> {
 
     /// Get the property types for this node type
     fn property_types() -> serde_json::Value;
+
+    /// Bind the node's relation fields to this node so they can be loaded
+    fn bind_relations(&mut self) {}
+
+    /// Get the string properties searched by [`crate::KuzuGraph::search`], marked `#[fulltext]`
+    fn fulltext_fields() -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    /// Get the embedding properties stored as `FLOAT[dim]` arrays, with their dimensions
+    fn vector_fields() -> Vec<(&'static str, usize)> {
+        Vec::new()
+    }
+
+    /// Get the indexes declared on properties with `#[unique]`
+    fn indexes() -> Vec<(&'static str, IndexKind)> {
+        Vec::new()
+    }
+
+    /// Check the `#[validate(...)]` rules on the node's fields
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Ok(())
+    }
+
+    /// Get the Cypher `DEFAULT` expressions declared with `#[property(default = ..)]`
+    fn column_defaults() -> Vec<(&'static str, &'static str)> {
+        Vec::new()
+    }
+
+    /// Get the values used for properties that are missing or null when a node is read
+    ///
+    /// Only properties that opt in with `#[property(default = ..)]` or `#[serde(default)]`
+    /// have one; other properties must be present when the node is read.
+    fn default_properties() -> serde_json::Map<String, serde_json::Value> {
+        serde_json::Map::new()
+    }
 }
 
 /// Trait for relationship types in the graph
@@ -30,18 +76,49 @@ pub trait Relationship: Serialize + for<'de> Deserialize<'de> {
     
     /// Get the target node ID 
     fn to(&self) -> String;
+
+    /// Get the source node's primary key as a query parameter
+    fn source_key(&self) -> Value {
+        Value::String(self.from())
+    }
+
+    /// Get the target node's primary key as a query parameter
+    fn target_key(&self) -> Value {
+        Value::String(self.to())
+    }
+
+    /// Get the primary key field of the source node type
+    fn source_key_field() -> &'static str {
+        "id"
+    }
+
+    /// Get the primary key field of the target node type
+    fn target_key_field() -> &'static str {
+        "id"
+    }
+
+    /// Name of the field marked `#[from_node]`
+    fn from_field() -> &'static str;
+
+    /// Name of the field marked `#[to_node]`
+    fn to_field() -> &'static str;
     
     /// Get the properties as a JSON value
     fn properties(&self) -> serde_json::Value {
         serde_json::to_value(self).unwrap()
     }
     
-    /// Get the source node label
+    /// Get the source node label, from the type in `#[from_node(NodeType)]`
     fn from_node_label() -> &'static str;
     
-    /// Get the target node label
+    /// Get the target node label, from the type in `#[to_node(NodeType)]`
     fn to_node_label() -> &'static str;
     
     /// Get the property definitions
     fn property_definitions() -> Vec<String>;
+
+    /// Check the `#[validate(...)]` rules on the relationship's fields
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Ok(())
+    }
 } 
\ No newline at end of file
@@ -0,0 +1,550 @@
+use crate::{Result, Error, Node, Relationship, KuzuGraph, FromValue, PrimaryKeys, Path, WeightedPath, KeyType};
+use kuzu::Value;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
+
+/// Direction in which relationships are followed from a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Follow relationships that start at the node
+    Outgoing,
+    /// Follow relationships that end at the node
+    Incoming,
+    /// Follow relationships in either direction
+    Both,
+}
+
+impl Direction {
+    /// Renders a relationship pattern such as `-[r:FOLLOWS]->` in this direction
+    pub(crate) fn pattern(&self, rel: &str) -> String {
+        match self {
+            Direction::Outgoing => format!("-[{}]->", rel),
+            Direction::Incoming => format!("<-[{}]-", rel),
+            Direction::Both => format!("-[{}]-", rel),
+        }
+    }
+}
+
+impl KuzuGraph {
+    /// Start a multi-hop traversal from a node
+    ///
+    /// # Arguments
+    /// * `id` - Primary key of the start node
+    ///
+    /// # Returns
+    /// * A builder on which to choose the relationship type with `via`
+    pub fn traverse<N: Node>(&self, id: impl Into<N::Key>) -> TraversalStart<'_, N> {
+        TraversalStart {
+            graph: self,
+            id: id.into().to_value(),
+            node_type: PhantomData,
+        }
+    }
+
+    /// Find a shortest path of relationships of type `R` between two nodes
+    ///
+    /// To allow other relationship labels on the path, use
+    /// [`Traversal::labels`] with [`Traversal::shortest_to`] instead.
+    ///
+    /// # Arguments
+    /// * `from_id` - Primary key of the start node
+    /// * `to_id` - Primary key of the end node
+    /// * `max_hops` - Maximum path length to search
+    ///
+    /// # Returns
+    /// * `Result<Option<Path<N, R>>>` - A shortest path, or None if the nodes are not connected
+    pub fn shortest_path<R, N>(&self, from_id: impl Into<N::Key>, to_id: impl Into<N::Key>, max_hops: u32) -> Result<Option<Path<N, R>>>
+    where
+        R: Relationship + FromValue,
+        N: Node + FromValue,
+    {
+        self.traverse::<N>(from_id)
+            .via::<R>()
+            .hops(1..=max_hops)
+            .shortest_to::<N>(to_id)
+    }
+
+    /// Find every shortest path of relationships of type `R` between two nodes
+    ///
+    /// # Arguments
+    /// * `from_id` - Primary key of the start node
+    /// * `to_id` - Primary key of the end node
+    /// * `max_hops` - Maximum path length to search
+    ///
+    /// # Returns
+    /// * `Result<Vec<Path<N, R>>>` - All paths of minimal length, empty if the nodes are not connected
+    pub fn all_shortest_paths<R, N>(&self, from_id: impl Into<N::Key>, to_id: impl Into<N::Key>, max_hops: u32) -> Result<Vec<Path<N, R>>>
+    where
+        R: Relationship + FromValue,
+        N: Node + FromValue,
+    {
+        self.traverse::<N>(from_id)
+            .via::<R>()
+            .hops(1..=max_hops)
+            .all_shortest_to::<N>(to_id)
+    }
+
+    /// Find the path between two nodes with the lowest total weight
+    ///
+    /// The weight of each relationship is computed on the client, so the search runs
+    /// as Dijkstra's algorithm over [`KuzuGraph::outgoing`] queries.
+    ///
+    /// # Arguments
+    /// * `from_id` - Primary key of the start node
+    /// * `to_id` - Primary key of the end node
+    /// * `weight` - Non-negative weight of a relationship
+    ///
+    /// # Returns
+    /// * `Result<Option<WeightedPath<N, R>>>` - A lightest path, or None if the nodes are not connected
+    pub fn weighted_shortest_path<R, N, F>(&self, from_id: impl Into<N::Key>, to_id: impl Into<N::Key>, weight: F) -> Result<Option<WeightedPath<N, R>>>
+    where
+        R: Relationship + FromValue,
+        N: Node + FromValue,
+        F: Fn(&R) -> f64,
+    {
+        self.dijkstra(from_id.into(), to_id.into(), |rel: &R| Ok(weight(rel)))
+    }
+
+    /// Find the path between two nodes with the lowest total value of a relationship property
+    ///
+    /// Kuzu 0.7 has no weighted path semantics, so this runs the same client-side
+    /// Dijkstra search as [`KuzuGraph::weighted_shortest_path`].
+    ///
+    /// # Arguments
+    /// * `from_id` - Primary key of the start node
+    /// * `to_id` - Primary key of the end node
+    /// * `field` - Numeric, non-negative relationship property to minimize
+    ///
+    /// # Returns
+    /// * `Result<Option<WeightedPath<N, R>>>` - A lightest path, or None if the nodes are not connected
+    pub fn weighted_shortest_path_by_field<R, N>(&self, from_id: impl Into<N::Key>, to_id: impl Into<N::Key>, field: &str) -> Result<Option<WeightedPath<N, R>>>
+    where
+        R: Relationship + FromValue,
+        N: Node + FromValue,
+    {
+        let numeric = R::property_definitions().iter().any(|definition| {
+            matches!(
+                definition.split_once(' '),
+                Some((name, "INT64" | "DOUBLE")) if name == field
+            )
+        });
+        if !numeric {
+            return Err(Error::InvalidPropertyType(
+                format!("{} relationship has no numeric property {}", R::label(), field)
+            ));
+        }
+
+        self.dijkstra(from_id.into(), to_id.into(), |rel: &R| field_weight(rel, field))
+    }
+
+    fn dijkstra<R, N, F>(&self, from_id: N::Key, to_id: N::Key, weight: F) -> Result<Option<WeightedPath<N, R>>>
+    where
+        R: Relationship + FromValue,
+        N: Node + FromValue,
+        F: Fn(&R) -> Result<f64>,
+    {
+        let start = match self.find_node::<N>(from_id.clone())? {
+            Some(start) => start,
+            None => return Ok(None),
+        };
+
+        // Nodes are tracked by the string form of their keys
+        let from_id = from_id.to_key_string();
+        let to_id = to_id.to_key_string();
+
+        let mut distances = HashMap::from([(from_id.to_string(), 0.0)]);
+        let mut nodes = HashMap::from([(from_id.to_string(), start)]);
+        let mut previous: HashMap<String, (String, R)> = HashMap::new();
+        let mut visited = HashSet::new();
+        let mut queue = BinaryHeap::from([Visit { distance: 0.0, id: from_id.to_string() }]);
+
+        while let Some(Visit { distance, id }) = queue.pop() {
+            if !visited.insert(id.clone()) {
+                continue;
+            }
+            if id == to_id {
+                break;
+            }
+
+            for (rel, next) in self.outgoing::<R, N, N>(nodes[&id].primary_key())? {
+                let w = weight(&rel)?;
+                if w < 0.0 {
+                    return Err(Error::InvalidPropertyType(
+                        format!("Negative weight {} on {} relationship", w, R::label())
+                    ));
+                }
+
+                let next_id = next.primary_key().to_key_string();
+                let next_distance = distance + w;
+                if distances.get(&next_id).is_none_or(|d| next_distance < *d) {
+                    distances.insert(next_id.clone(), next_distance);
+                    previous.insert(next_id.clone(), (id.clone(), rel));
+                    nodes.insert(next_id.clone(), next);
+                    queue.push(Visit { distance: next_distance, id: next_id });
+                }
+            }
+        }
+
+        let weight = match distances.get(&to_id) {
+            Some(weight) => *weight,
+            None => return Ok(None),
+        };
+
+        // Walk back from the target to rebuild the path
+        let mut path_nodes = Vec::new();
+        let mut relationships = Vec::new();
+        let mut current = to_id;
+        while let Some((prev, rel)) = previous.remove(&current) {
+            path_nodes.push(nodes.remove(&current).unwrap());
+            relationships.push(rel);
+            current = prev;
+        }
+        path_nodes.push(nodes.remove(&current).unwrap());
+        path_nodes.reverse();
+        relationships.reverse();
+
+        Ok(Some(WeightedPath {
+            path: Path { nodes: path_nodes, relationships },
+            weight,
+        }))
+    }
+
+    /// Find the relationships of type `R` leaving a node, with the nodes they point to
+    ///
+    /// # Arguments
+    /// * `from_id` - Primary key of the source node
+    ///
+    /// # Returns
+    /// * `Result<Vec<(R, To)>>` - Each relationship paired with its target node
+    pub fn outgoing<R, From, To>(&self, from_id: impl Into<From::Key>) -> Result<Vec<(R, To)>>
+    where
+        R: Relationship + FromValue,
+        From: Node,
+        To: Node + FromValue,
+    {
+        self.neighbors::<R, From, To>(from_id, Direction::Outgoing)
+    }
+
+    /// Find the relationships of type `R` arriving at a node, with the nodes they come from
+    ///
+    /// # Arguments
+    /// * `to_id` - Primary key of the target node
+    ///
+    /// # Returns
+    /// * `Result<Vec<(R, From)>>` - Each relationship paired with its source node
+    pub fn incoming<R, To, From>(&self, to_id: impl Into<To::Key>) -> Result<Vec<(R, From)>>
+    where
+        R: Relationship + FromValue,
+        To: Node,
+        From: Node + FromValue,
+    {
+        self.neighbors::<R, To, From>(to_id, Direction::Incoming)
+    }
+
+    /// Find the relationships of type `R` attached to a node of type `S`, with the
+    /// nodes of type `N` on their other end
+    ///
+    /// # Arguments
+    /// * `id` - Primary key of the node to start from
+    /// * `direction` - Which relationships to follow
+    ///
+    /// # Returns
+    /// * `Result<Vec<(R, N)>>` - Each relationship paired with the neighboring node
+    pub fn neighbors<R, S, N>(&self, id: impl Into<S::Key>, direction: Direction) -> Result<Vec<(R, N)>>
+    where
+        R: Relationship + FromValue,
+        S: Node,
+        N: Node + FromValue,
+    {
+        let query = format!(
+            "MATCH (a:{}){}(b:{}) WHERE a.{} = $id RETURN r, b, a",
+            S::label(),
+            direction.pattern(&format!("r:{}", R::label())),
+            N::label(),
+            S::primary_key_field()
+        );
+
+        // The start node is returned too, so the relationship's endpoints can be resolved
+        let mut keys = PrimaryKeys::default();
+        keys.register::<S>();
+
+        self.query_as_with_keys(&query, vec![("id", id.into().to_value())], keys)
+    }
+}
+
+/// A traversal whose start node is known but whose relationship type is not
+pub struct TraversalStart<'g, N> {
+    graph: &'g KuzuGraph,
+    id: Value,
+    node_type: PhantomData<N>,
+}
+
+impl<'g, N: Node> TraversalStart<'g, N> {
+    /// Follows relationships of type `R`
+    #[must_use]
+    pub fn via<R: Relationship>(self) -> Traversal<'g, N, R> {
+        Traversal {
+            graph: self.graph,
+            id: self.id,
+            min_hops: None,
+            max_hops: None,
+            direction: Direction::Outgoing,
+            labels: vec![R::label().to_string()],
+            node_conditions: Vec::new(),
+            rel_conditions: Vec::new(),
+            types: PhantomData,
+        }
+    }
+}
+
+/// Which paths a recursive relationship pattern matches
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathSemantics {
+    /// Every path within the hop range
+    Walk,
+    /// A single shortest path
+    Shortest,
+    /// Every path of minimal length
+    AllShortest,
+}
+
+/// A variable-length traversal over relationships of type `R`
+///
+/// Compiles to Kuzu's recursive relationship syntax, e.g. `-[e:FOLLOWS*1..3]->`.
+pub struct Traversal<'g, N, R> {
+    graph: &'g KuzuGraph,
+    id: Value,
+    min_hops: Option<u32>,
+    max_hops: Option<u32>,
+    direction: Direction,
+    labels: Vec<String>,
+    node_conditions: Vec<String>,
+    rel_conditions: Vec<String>,
+    types: PhantomData<(N, R)>,
+}
+
+impl<N: Node, R: Relationship> Traversal<'_, N, R> {
+    /// Restricts the number of hops, e.g. `1..=3` or `2..`
+    ///
+    /// # Arguments
+    /// * `hops` - Range of allowed path lengths
+    ///
+    /// # Returns
+    /// * Self for method chaining
+    #[must_use]
+    pub fn hops<B: RangeBounds<u32>>(mut self, hops: B) -> Self {
+        self.min_hops = match hops.start_bound() {
+            Bound::Included(n) => Some(*n),
+            Bound::Excluded(n) => Some(n + 1),
+            Bound::Unbounded => None,
+        };
+        self.max_hops = match hops.end_bound() {
+            Bound::Included(n) => Some(*n),
+            Bound::Excluded(n) => Some(n.saturating_sub(1)),
+            Bound::Unbounded => None,
+        };
+        self
+    }
+
+    /// Sets the direction relationships are followed in
+    ///
+    /// # Arguments
+    /// * `direction` - Direction to follow, `Outgoing` by default
+    ///
+    /// # Returns
+    /// * Self for method chaining
+    #[must_use]
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Sets the relationship labels the path may use, instead of only `R`'s label
+    ///
+    /// Every relationship on the path is still deserialized as `R`, so the
+    /// tables must share its properties.
+    ///
+    /// # Arguments
+    /// * `labels` - The allowed relationship labels
+    ///
+    /// # Returns
+    /// * Self for method chaining
+    #[must_use]
+    pub fn labels<S: AsRef<str>>(mut self, labels: &[S]) -> Self {
+        self.labels = labels.iter().map(|l| l.as_ref().to_string()).collect();
+        self
+    }
+
+    /// Adds a condition that every intermediate node must satisfy
+    ///
+    /// # Arguments
+    /// * `condition` - The condition, referring to the node as `n`
+    ///
+    /// # Returns
+    /// * Self for method chaining
+    #[must_use]
+    pub fn where_node<S: Into<String>>(mut self, condition: S) -> Self {
+        self.node_conditions.push(condition.into());
+        self
+    }
+
+    /// Adds a condition that every relationship on the path must satisfy
+    ///
+    /// # Arguments
+    /// * `condition` - The condition, referring to the relationship as `r`
+    ///
+    /// # Returns
+    /// * Self for method chaining
+    #[must_use]
+    pub fn where_relationship<S: Into<String>>(mut self, condition: S) -> Self {
+        self.rel_conditions.push(condition.into());
+        self
+    }
+
+    /// Builds the Cypher query for paths ending at nodes labelled `end_label`
+    ///
+    /// # Returns
+    /// * A string containing the complete Cypher query
+    pub fn build(&self, end_label: &str) -> String {
+        self.render(&PathSemantics::Walk, end_label, None)
+    }
+
+    fn render(&self, semantics: &PathSemantics, end_label: &str, end_pk: Option<&str>) -> String {
+        let mut rel = format!("e:{}*", self.labels.join("|"));
+        let range = (self.min_hops.is_some() || self.max_hops.is_some()).then(|| format!(
+            "{}..{}",
+            self.min_hops.map(|n| n.to_string()).unwrap_or_default(),
+            self.max_hops.map(|n| n.to_string()).unwrap_or_default()
+        ));
+        let keyword = match semantics {
+            PathSemantics::Walk => None,
+            PathSemantics::Shortest => Some("SHORTEST"),
+            PathSemantics::AllShortest => Some("ALL SHORTEST"),
+        };
+
+        // Kuzu separates the keyword from the range, e.g. `* SHORTEST 1..3` vs `*1..3`
+        if let Some(keyword) = keyword {
+            rel.push_str(&format!(" {}", keyword));
+            if range.is_some() {
+                rel.push(' ');
+            }
+        }
+        if let Some(range) = range {
+            rel.push_str(&range);
+        }
+
+        let conditions: Vec<&str> = self.rel_conditions.iter()
+            .chain(&self.node_conditions)
+            .map(String::as_str)
+            .collect();
+        if !conditions.is_empty() {
+            rel.push_str(&format!(" (r, n | WHERE {})", conditions.join(" AND ")));
+        }
+
+        let mut query = format!(
+            "MATCH (a:{}){}(b:{}) WHERE a.{} = $id",
+            N::label(),
+            self.direction.pattern(&rel),
+            end_label,
+            N::primary_key_field()
+        );
+        if let Some(end_pk) = end_pk {
+            query.push_str(&format!(" AND b.{} = $to", end_pk));
+        }
+        query.push_str(" RETURN a, e, b");
+        query
+    }
+
+    fn run<M>(&self, semantics: &PathSemantics, to_id: Option<Value>) -> Result<Vec<Path<M, R>>>
+    where
+        M: Node + FromValue,
+        R: FromValue,
+    {
+        let query = self.render(semantics, M::label(), to_id.as_ref().map(|_| M::primary_key_field()));
+        let mut params = vec![("id", self.id.clone())];
+        if let Some(to_id) = to_id {
+            params.push(("to", to_id));
+        }
+
+        let mut keys = PrimaryKeys::default();
+        keys.register::<N>();
+        self.graph.query_as_with_keys(&query, params, keys)
+    }
+
+    /// Runs the traversal and returns every matching path ending at a node of type `M`
+    ///
+    /// All nodes on the path are deserialized as `M`, so the start and intermediate
+    /// nodes must share its shape.
+    ///
+    /// # Returns
+    /// * `Result<Vec<Path<M, R>>>` - The matching paths or an error
+    pub fn collect<M>(&self) -> Result<Vec<Path<M, R>>>
+    where
+        M: Node + FromValue,
+        R: FromValue,
+    {
+        self.run(&PathSemantics::Walk, None)
+    }
+
+    /// Finds a shortest path from the start node to the node with primary key `to_id`
+    ///
+    /// # Returns
+    /// * `Result<Option<Path<M, R>>>` - A shortest path, or None if there is none within the hop range
+    pub fn shortest_to<M>(&self, to_id: impl Into<M::Key>) -> Result<Option<Path<M, R>>>
+    where
+        M: Node + FromValue,
+        R: FromValue,
+    {
+        Ok(self.run(&PathSemantics::Shortest, Some(to_id.into().to_value()))?.into_iter().next())
+    }
+
+    /// Finds every shortest path from the start node to the node with primary key `to_id`
+    ///
+    /// # Returns
+    /// * `Result<Vec<Path<M, R>>>` - All paths of minimal length within the hop range
+    pub fn all_shortest_to<M>(&self, to_id: impl Into<M::Key>) -> Result<Vec<Path<M, R>>>
+    where
+        M: Node + FromValue,
+        R: FromValue,
+    {
+        self.run(&PathSemantics::AllShortest, Some(to_id.into().to_value()))
+    }
+}
+
+/// Entry in Dijkstra's priority queue, ordered so the closest node pops first
+struct Visit {
+    distance: f64,
+    id: String,
+}
+
+impl PartialEq for Visit {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Visit {}
+
+impl PartialOrd for Visit {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Visit {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance.total_cmp(&self.distance)
+    }
+}
+
+/// Reads a numeric property off a relationship by name
+fn field_weight<R: Relationship>(rel: &R, field: &str) -> Result<f64> {
+    rel.properties()
+        .get(field)
+        .and_then(|value| value.as_f64())
+        .ok_or_else(|| Error::InvalidPropertyType(
+            format!("{} relationship has no numeric property {}", R::label(), field)
+        ))
+}
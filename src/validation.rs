@@ -0,0 +1,197 @@
+use regex::Regex;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
+
+/// A single failed validation rule on a field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// The field that failed validation
+    pub field: String,
+    /// The rule that failed: `range`, `length`, `regex` or `custom`
+    pub rule: &'static str,
+    /// A human-readable description of the failure
+    pub message: String,
+}
+
+/// Every validation failure on a node or relationship, returned inside
+/// [`Error::Validation`](crate::Error::Validation)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationErrors(Vec<ValidationError>);
+
+impl ValidationErrors {
+    /// Creates an empty list of validation errors
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a failed rule on a field
+    ///
+    /// # Arguments
+    /// * `field` - The field that failed validation
+    /// * `rule` - The name of the rule that failed
+    /// * `message` - A description of the failure
+    pub fn add(&mut self, field: &str, rule: &'static str, message: impl Into<String>) {
+        self.0.push(ValidationError {
+            field: field.to_string(),
+            rule,
+            message: message.into(),
+        });
+    }
+
+    /// Whether no rule failed
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The failed rules, in declaration order
+    pub fn errors(&self) -> &[ValidationError] {
+        &self.0
+    }
+
+    /// The failed rules on one field
+    pub fn field(&self, field: &str) -> Vec<&ValidationError> {
+        self.0.iter().filter(|error| error.field == field).collect()
+    }
+
+    /// Converts the list into a result, failing if any rule failed
+    ///
+    /// # Returns
+    /// * `Result<(), ValidationErrors>` - `Ok` if the list is empty
+    pub fn into_result(self) -> Result<(), ValidationErrors> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let messages: Vec<String> = self.0
+            .iter()
+            .map(|error| format!("{}: {}", error.field, error.message))
+            .collect();
+        f.write_str(&messages.join("; "))
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+/// Values with a length, for `#[validate(length(...))]`
+pub trait HasLength {
+    /// The length in characters for strings and elements for collections
+    fn length(&self) -> usize;
+}
+
+impl HasLength for String {
+    fn length(&self) -> usize {
+        self.chars().count()
+    }
+}
+
+impl HasLength for &str {
+    fn length(&self) -> usize {
+        self.chars().count()
+    }
+}
+
+impl<T> HasLength for Vec<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Checks `#[validate(range(min = .., max = ..))]` on a numeric field
+#[doc(hidden)]
+pub fn check_range(errors: &mut ValidationErrors, field: &str, value: f64, min: Option<f64>, max: Option<f64>) {
+    let below = min.is_some_and(|min| value < min);
+    let above = max.is_some_and(|max| value > max);
+    if below || above {
+        let bounds = match (min, max) {
+            (Some(min), Some(max)) => format!("between {} and {}", min, max),
+            (Some(min), None) => format!("at least {}", min),
+            (None, Some(max)) => format!("at most {}", max),
+            (None, None) => unreachable!(),
+        };
+        errors.add(field, "range", format!("must be {}, got {}", bounds, value));
+    }
+}
+
+/// Checks `#[validate(length(min = .., max = ..))]` on a string or collection field
+#[doc(hidden)]
+pub fn check_length<T: HasLength + ?Sized>(errors: &mut ValidationErrors, field: &str, value: &T, min: Option<usize>, max: Option<usize>) {
+    let length = value.length();
+    if min.is_some_and(|min| length < min) {
+        errors.add(field, "length", format!("must have a length of at least {}, got {}", min.unwrap(), length));
+    }
+    if max.is_some_and(|max| length > max) {
+        errors.add(field, "length", format!("must have a length of at most {}, got {}", max.unwrap(), length));
+    }
+}
+
+/// Checks `#[validate(regex = "..")]` on a string field
+///
+/// Patterns are compiled once and cached for the life of the process.
+#[doc(hidden)]
+pub fn check_regex(errors: &mut ValidationErrors, field: &str, value: &str, pattern: &'static str) {
+    static PATTERNS: OnceLock<Mutex<HashMap<&'static str, Regex>>> = OnceLock::new();
+
+    let mut patterns = PATTERNS.get_or_init(Default::default).lock().unwrap();
+    let regex = match patterns.get(pattern) {
+        Some(regex) => regex,
+        None => match Regex::new(pattern) {
+            Ok(regex) => patterns.entry(pattern).or_insert(regex),
+            Err(e) => {
+                errors.add(field, "regex", format!("has an invalid pattern {:?}: {}", pattern, e));
+                return;
+            }
+        },
+    };
+
+    if !regex.is_match(value) {
+        errors.add(field, "regex", format!("must match the pattern {:?}", pattern));
+    }
+}
+
+/// Records the result of a `#[validate(custom = "..")]` function
+#[doc(hidden)]
+pub fn check_custom(errors: &mut ValidationErrors, field: &str, result: Result<(), String>) {
+    if let Err(message) = result {
+        errors.add(field, "custom", message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules() {
+        let mut errors = ValidationErrors::new();
+        check_range(&mut errors, "age", 42.0, Some(0.0), Some(150.0));
+        check_length(&mut errors, "name", &"Zoë".to_string(), Some(1), Some(3));
+        check_regex(&mut errors, "email", "a@example.com", "^[^@]+@[^@]+$");
+        check_custom(&mut errors, "name", Ok(()));
+        assert!(errors.is_empty());
+
+        check_range(&mut errors, "age", -1.0, Some(0.0), Some(150.0));
+        check_length(&mut errors, "tags", &vec![1, 2, 3], None, Some(2));
+        check_regex(&mut errors, "email", "not an email", "^[^@]+@[^@]+$");
+        check_regex(&mut errors, "email", "a", "(");
+        check_custom(&mut errors, "name", Err("is reserved".to_string()));
+
+        let rules: Vec<_> = errors.errors().iter().map(|e| (e.field.as_str(), e.rule)).collect();
+        assert_eq!(rules, vec![
+            ("age", "range"),
+            ("tags", "length"),
+            ("email", "regex"),
+            ("email", "regex"),
+            ("name", "custom"),
+        ]);
+        assert_eq!(errors.field("email").len(), 2);
+        assert_eq!(errors.errors()[0].message, "must be between 0 and 150, got -1");
+        assert!(errors.into_result().is_err());
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{Result, Error, Node, KuzuGraph, FromValue, QueryBuilder};
+use crate::query::Fragment;
+use kuzu::{LogicalType, Value};
+
+/// Similarity measure for [`KuzuGraph::nearest`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// Cosine similarity; higher scores are closer
+    Cosine,
+    /// Euclidean distance; lower scores are closer
+    Euclidean,
+    /// Inner product; higher scores are closer
+    InnerProduct,
+}
+
+impl Metric {
+    /// The Kuzu array function computing this metric
+    fn function(&self) -> &'static str {
+        match self {
+            Metric::Cosine => "ARRAY_COSINE_SIMILARITY",
+            Metric::Euclidean => "ARRAY_DISTANCE",
+            Metric::InnerProduct => "ARRAY_INNER_PRODUCT",
+        }
+    }
+
+    /// The sort order that puts the closest nodes first
+    fn order(&self) -> &'static str {
+        match self {
+            Metric::Euclidean => "ASC",
+            Metric::Cosine | Metric::InnerProduct => "DESC",
+        }
+    }
+}
+
+/// Column definitions for the `#[vector]` fields of `N`
+pub(crate) fn vector_columns<N: Node>() -> Vec<String> {
+    N::vector_fields()
+        .into_iter()
+        .map(|(field, dim)| format!("{} FLOAT[{}]", field, dim))
+        .collect()
+}
+
+/// The dimension of a `#[vector]` field of `N`, if it is one
+pub(crate) fn vector_dim<N: Node>(field: &str) -> Option<usize> {
+    N::vector_fields()
+        .into_iter()
+        .find(|(name, _)| *name == field)
+        .map(|(_, dim)| dim)
+}
+
+/// Converts a JSON array of numbers into a kuzu `FLOAT` array
+pub(crate) fn vector_value(json: &serde_json::Value) -> Option<Value> {
+    let items = json.as_array()?;
+    let values = items
+        .iter()
+        .map(|item| item.as_f64().map(|f| Value::Float(f as f32)))
+        .collect::<Option<Vec<_>>>()?;
+    Some(Value::Array(LogicalType::Float, values))
+}
+
+impl<N: Node> QueryBuilder<N> {
+    /// Finds the nodes whose vector field is closest to a query vector,
+    /// among the nodes matching this query's conditions
+    ///
+    /// Kuzu 0.7.1 has no vector index, so this is an exact, brute-force search: the
+    /// metric is computed for every matching node and the results sorted, which is
+    /// O(n) in the number of nodes. Conditions narrow the scan.
+    ///
+    /// # Arguments
+    /// * `graph` - The graph to search
+    /// * `field` - A `#[vector]` field of `N`
+    /// * `vector` - The query vector, with the field's dimension
+    /// * `k` - Maximum number of results to return
+    /// * `metric` - How closeness is measured
+    ///
+    /// # Returns
+    /// * `Result<Vec<(N, f64)>>` - Matching nodes with their scores, closest first
+    pub fn nearest(
+        &self,
+        graph: &KuzuGraph,
+        field: &str,
+        vector: &[f32],
+        k: usize,
+        metric: Metric,
+    ) -> Result<Vec<(N, f64)>>
+    where
+        N: FromValue,
+    {
+        let dim = vector_dim::<N>(field).ok_or_else(|| Error::Query(
+            format!("{} has no vector property {}", N::label(), field)
+        ))?;
+        if vector.len() != dim {
+            return Err(Error::Query(format!(
+                "Expected a vector of dimension {} for {}.{}, got {}",
+                dim, N::label(), field, vector.len()
+            )));
+        }
+
+        let mut items = Fragment::from(format!("n, {}(n.{}, ", metric.function(), field));
+        items.push_param(Value::Array(
+            LogicalType::Float,
+            vector.iter().map(|f| Value::Float(*f)).collect(),
+        ));
+        items.push_str(") AS score");
+
+        let (text, params) = self.clone()
+            .limit(k)
+            .render_stage("RETURN", items, &format!(" ORDER BY score {}", metric.order()))
+            .finish()?;
+        graph.query_as(&text, params.as_vec())
+    }
+}
+
+impl KuzuGraph {
+    /// Finds the nodes whose vector field is closest to a query vector
+    ///
+    /// This scans every node of type `N` rather than using a vector index, which
+    /// Kuzu 0.7.1 does not have; see [`QueryBuilder::nearest`], which also restricts
+    /// the search with conditions on other properties.
+    ///
+    /// # Arguments
+    /// * `field` - A `#[vector]` field of `N`
+    /// * `vector` - The query vector, with the field's dimension
+    /// * `k` - Maximum number of results to return
+    /// * `metric` - How closeness is measured
+    ///
+    /// # Returns
+    /// * `Result<Vec<(N, f64)>>` - The closest nodes with their scores, closest first
+    pub fn nearest<N: Node + FromValue>(
+        &self,
+        field: &str,
+        vector: &[f32],
+        k: usize,
+        metric: Metric,
+    ) -> Result<Vec<(N, f64)>> {
+        QueryBuilder::<N>::new().nearest(self, field, vector, k, metric)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_vector_value() {
+        match vector_value(&json!([0.5, 1, -2.0])) {
+            Some(Value::Array(LogicalType::Float, values)) => {
+                assert!(matches!(values.as_slice(), [Value::Float(a), Value::Float(b), Value::Float(c)]
+                    if *a == 0.5 && *b == 1.0 && *c == -2.0));
+            },
+            other => panic!("Expected a float array, got {:?}", other),
+        }
+        assert!(vector_value(&json!(["a"])).is_none());
+        assert!(vector_value(&json!(1.0)).is_none());
+    }
+}
@@ -0,0 +1,119 @@
+mod common;
+
+#[cfg(test)]
+mod algo_tests {
+    use okodata::{Node, Relationship, KuzuGraph, Direction};
+    use okodata::algo::{write_back, NodeKey, ProjectionBuilder};
+    use serde::{Serialize, Deserialize};
+    use tempfile::TempDir;
+    use crate::common;
+
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("User")]
+    struct User {
+        #[id]
+        id: String,
+        #[property]
+        name: String,
+        #[property]
+        age: u32,
+    }
+
+    #[derive(Relationship, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("FOLLOWS")]
+    struct Follows {
+        #[from_node]
+        from: String,
+        #[to_node]
+        to: String,
+        #[property]
+        since: u32,
+    }
+
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("Post")]
+    struct Post {
+        #[id]
+        slug: String,
+    }
+
+    #[derive(Relationship, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("WROTE")]
+    struct Wrote {
+        #[from_node(User)]
+        author: String,
+        #[to_node(Post)]
+        post: String,
+    }
+
+    /// Alice, Bob and Carol follow each other in a cycle; Dave follows Alice
+    fn setup_test_db() -> (TempDir, KuzuGraph) {
+        let (dir, db) = common::test_db();
+        db.create_node_table::<User>().unwrap();
+        db.create_relationship_table::<Follows>().unwrap();
+
+        for (id, name) in [("u1", "Alice"), ("u2", "Bob"), ("u3", "Carol"), ("u4", "Dave")] {
+            db.create_node(&User { id: id.to_string(), name: name.to_string(), age: 0 }).unwrap();
+        }
+        for (from, to) in [("u1", "u2"), ("u2", "u3"), ("u3", "u1"), ("u4", "u1")] {
+            db.create_relationship(&Follows { from: from.to_string(), to: to.to_string(), since: 2024 }).unwrap();
+        }
+
+        (dir, db)
+    }
+
+    #[test]
+    fn test_projection_algorithms_and_write_back() {
+        let (_dir, db) = setup_test_db();
+
+        let projection = ProjectionBuilder::new()
+            .node::<User>()
+            .relationship::<Follows>()
+            .load(&db)
+            .unwrap();
+        assert_eq!(projection.node_count(), 4);
+        assert_eq!(projection.edge_count(), 4);
+
+        let ranks = projection.page_rank(0.85, 30);
+        let alice = NodeKey::new("User", "u1");
+        assert!(ranks[&alice] > ranks[&NodeKey::new("User", "u4")]);
+
+        let in_degree = projection.degree_centrality(Direction::Incoming);
+        assert!(in_degree[&alice] > in_degree[&NodeKey::new("User", "u2")]);
+
+        // Store each user's triangle count in their age
+        let triangles = projection.triangle_count();
+        let updated = write_back::<User, _, _>(&db, &triangles, |user, count| {
+            user.age = *count as u32;
+        }).unwrap();
+        assert_eq!(updated, 4);
+
+        let alice: User = db.find_node("u1").unwrap().unwrap();
+        let dave: User = db.find_node("u4").unwrap().unwrap();
+        assert_eq!(alice.age, 1);
+        assert_eq!(dave.age, 0);
+    }
+
+    #[test]
+    fn test_projection_across_node_types() {
+        let (_dir, db) = setup_test_db();
+        assert_eq!((Wrote::from_node_label(), Wrote::to_node_label()), ("User", "Post"));
+
+        db.create_node_table::<Post>().unwrap();
+        db.create_relationship_table::<Wrote>().unwrap();
+        db.create_node(&Post { slug: "hello".to_string() }).unwrap();
+        db.create_relationship(&Wrote { author: "u2".to_string(), post: "hello".to_string() }).unwrap();
+
+        let projection = ProjectionBuilder::new()
+            .node::<User>()
+            .node::<Post>()
+            .relationship::<Wrote>()
+            .load(&db)
+            .unwrap();
+        assert_eq!(projection.node_count(), 5);
+        assert_eq!(projection.edge_count(), 1);
+
+        let in_degree = projection.degree_centrality(Direction::Incoming);
+        assert!(in_degree[&NodeKey::new("Post", "hello")] > 0.0);
+    }
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "arrow")]
+
+mod common;
+
+#[cfg(test)]
+mod arrow_tests {
+    use okodata::{Node, KuzuGraph, batch_to_nodes};
+    use serde::{Serialize, Deserialize};
+    use tempfile::TempDir;
+    use crate::common;
+
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("User")]
+    struct User {
+        #[id]
+        id: String,
+        #[property]
+        name: String,
+        #[property]
+        age: u32,
+    }
+
+    fn setup_test_db() -> (TempDir, KuzuGraph) {
+        let (dir, db) = common::test_db();
+        db.create_node_table::<User>().unwrap();
+
+        for i in 0..5 {
+            let user = User {
+                id: format!("u{}", i),
+                name: format!("User {}", i),
+                age: 20 + i,
+            };
+            db.create_node(&user).unwrap();
+        }
+
+        (dir, db)
+    }
+
+    #[test]
+    fn test_query_arrow_batches() {
+        let (_dir, db) = setup_test_db();
+
+        let batches = db
+            .query_arrow("MATCH (n:User) RETURN n.id AS id, n.name AS name, n.age AS age ORDER BY n.id", 2)
+            .unwrap()
+            .collect::<Vec<_>>();
+
+        assert_eq!(batches.len(), 3);
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 5);
+
+        let users: Vec<User> = batch_to_nodes(&batches[0]).unwrap();
+        assert_eq!(users[0].id, "u0");
+        assert_eq!(users[1].age, 21);
+    }
+
+    #[test]
+    fn test_batch_of_node_column() {
+        let (_dir, db) = setup_test_db();
+
+        // `RETURN n` produces a single struct column holding each node's properties
+        let batches: Vec<_> = db
+            .query_arrow("MATCH (n:User) RETURN n ORDER BY n.id", 10)
+            .unwrap()
+            .collect();
+        assert_eq!(batches.len(), 1);
+
+        let users: Vec<User> = batch_to_nodes(&batches[0]).unwrap();
+        assert_eq!(users.len(), 5);
+        assert_eq!(users[4], User { id: "u4".to_string(), name: "User 4".to_string(), age: 24 });
+    }
+}
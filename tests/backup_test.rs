@@ -0,0 +1,51 @@
+mod common;
+
+#[cfg(test)]
+mod backup_tests {
+    use okodata::{Node, KuzuGraph, Error, BackupManifest};
+    use serde::{Serialize, Deserialize};
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::common;
+
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("Account")]
+    struct Account {
+        #[id]
+        id: String,
+        #[property]
+        balance: i64,
+    }
+
+    fn setup_test_db() -> (TempDir, KuzuGraph) {
+        let (dir, db) = common::test_db();
+        db.create_node_table::<Account>().unwrap();
+        db.create_node(&Account { id: "acc1".to_string(), balance: 100 }).unwrap();
+        (dir, db)
+    }
+
+    #[test]
+    fn test_backup_and_restore() {
+        let (dir, db) = setup_test_db();
+        let (backup_path, restore_path) = (dir.path().join("backup"), dir.path().join("restored"));
+
+        let manifest = db.backup_to(&backup_path).unwrap();
+        assert_eq!(BackupManifest::read(&backup_path).unwrap(), manifest);
+
+        let restored = KuzuGraph::restore_from(&backup_path, &restore_path).unwrap();
+        let acc: Option<Account> = restored.find_node("acc1").unwrap();
+        assert_eq!(acc.unwrap().balance, 100);
+    }
+
+    #[test]
+    fn test_restore_rejects_modified_snapshot() {
+        let (dir, db) = setup_test_db();
+        let (backup_path, restore_path) = (dir.path().join("backup"), dir.path().join("restored"));
+
+        db.backup_to(&backup_path).unwrap();
+        fs::write(backup_path.join("schema.cypher"), "-- tampered").unwrap();
+
+        let result = KuzuGraph::restore_from(&backup_path, &restore_path);
+        assert!(matches!(result, Err(Error::Backup(_))));
+    }
+}
@@ -0,0 +1,17 @@
+//! Fixtures shared by the integration tests
+
+use okodata::KuzuGraph;
+use tempfile::TempDir;
+
+/// Opens an empty database in a new temporary directory
+///
+/// The database lives in the `db` subdirectory, leaving room for other files such
+/// as backups. Everything is removed when the `TempDir` is dropped, so keep it
+/// alive for as long as the database is used.
+pub fn test_db() -> (TempDir, KuzuGraph) {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("db");
+    std::fs::create_dir_all(&path).unwrap();
+    let db = KuzuGraph::new(path.to_str().unwrap()).unwrap();
+    (dir, db)
+}
@@ -0,0 +1,82 @@
+mod common;
+
+#[cfg(test)]
+mod default_tests {
+    use okodata::Node;
+    use serde::{Serialize, Deserialize};
+    use crate::common;
+
+    /// The first version of the `Profile` table
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("Profile")]
+    struct ProfileV1 {
+        #[id]
+        id: String,
+        #[property]
+        name: String,
+    }
+
+    /// A later version of the `Profile` table with three more properties
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("Profile")]
+    struct Profile {
+        #[id]
+        id: String,
+        #[property]
+        name: String,
+        #[property(default = 10)]
+        credits: i64,
+        #[property(default = "current_timestamp()")]
+        created: String,
+        #[property]
+        #[serde(default)]
+        nickname: String,
+    }
+
+    /// A property type without a `Default` impl
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Level(i64);
+
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("Badge")]
+    struct Badge {
+        #[id]
+        id: String,
+        #[property]
+        level: Level,
+    }
+
+    #[test]
+    fn test_column_defaults() {
+        assert_eq!(Profile::column_defaults(), vec![
+            ("credits", "10"),
+            ("created", "current_timestamp()"),
+        ]);
+
+        let defaults = Profile::default_properties();
+        let mut names: Vec<&str> = defaults.keys().map(String::as_str).collect();
+        names.sort();
+        assert_eq!(names, vec!["created", "credits", "nickname"]);
+        assert!(Badge::default_properties().is_empty());
+    }
+
+    #[test]
+    fn test_added_columns_use_defaults() {
+        let (_dir, db) = common::test_db();
+        db.create_node_table::<ProfileV1>().unwrap();
+        db.create_node(&ProfileV1 { id: "p1".to_string(), name: "Alice".to_string() }).unwrap();
+
+        // Creating the table for the new version adds the missing columns
+        db.create_node_table::<Profile>().unwrap();
+        let profile = db.find_node::<Profile>("p1").unwrap().unwrap();
+        assert_eq!(profile.name, "Alice");
+        assert_eq!(profile.credits, 10);
+        assert!(!profile.created.is_empty());
+
+        // Columns without a default are null and read as the #[serde(default)]
+        assert_eq!(profile.nickname, "");
+
+        // The old version can still be read
+        assert!(db.find_node::<ProfileV1>("p1").unwrap().is_some());
+    }
+}
@@ -0,0 +1,115 @@
+mod common;
+
+#[cfg(test)]
+mod index_tests {
+    use okodata::{Node, KuzuGraph, IndexKind, Error};
+    use serde::{Serialize, Deserialize};
+    use tempfile::TempDir;
+    use crate::common;
+
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("Account")]
+    struct Account {
+        #[id]
+        id: String,
+        #[unique]
+        email: String,
+        #[property]
+        bio: String,
+        #[property]
+        handle: String,
+    }
+
+    fn account(id: &str, email: &str, handle: &str) -> Account {
+        Account {
+            id: id.to_string(),
+            email: email.to_string(),
+            bio: format!("Account of {}", handle),
+            handle: handle.to_string(),
+        }
+    }
+
+    fn setup_test_db() -> (TempDir, KuzuGraph) {
+        let (dir, db) = common::test_db();
+        db.create_node_table::<Account>().unwrap();
+        (dir, db)
+    }
+
+    #[test]
+    fn test_declared_indexes() {
+        let (_dir, db) = setup_test_db();
+
+        let mut indexes: Vec<(String, IndexKind)> = db.list_indexes::<Account>()
+            .unwrap()
+            .into_iter()
+            .map(|index| (index.name, index.kind))
+            .collect();
+        indexes.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(indexes, vec![
+            ("Account_email_unique".to_string(), IndexKind::Unique),
+        ]);
+
+        // Creating the table again keeps the existing indexes
+        db.create_node_table::<Account>().unwrap();
+        assert_eq!(db.list_indexes::<Account>().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_unique_enforced_on_write() {
+        let (_dir, db) = setup_test_db();
+
+        db.create_node(&account("a1", "a@example.com", "alice")).unwrap();
+        match db.create_node(&account("a2", "a@example.com", "alex")) {
+            Err(Error::ConstraintViolation { label, field, value }) => {
+                assert_eq!((label.as_str(), field.as_str(), value.as_str()), ("Account", "email", "a@example.com"));
+            },
+            other => panic!("Expected a constraint violation, got {:?}", other),
+        }
+        assert!(db.find_node::<Account>("a2").unwrap().is_none());
+        db.create_node(&account("a2", "b@example.com", "alex")).unwrap();
+
+        // Rewriting a node with its own value is allowed
+        db.create_node(&account("a1", "a@example.com", "alice")).unwrap();
+        assert!(matches!(
+            db.update_node(&account("a2", "a@example.com", "alex")),
+            Err(Error::ConstraintViolation { .. })
+        ));
+        assert!(db.find_node::<Account>("a2").unwrap().unwrap().email == "b@example.com");
+    }
+
+    #[test]
+    fn test_upsert_node() {
+        let (_dir, db) = setup_test_db();
+
+        db.upsert_node(&account("a1", "a@example.com", "alice")).unwrap();
+        db.upsert_node(&account("a1", "a2@example.com", "alice")).unwrap();
+        assert_eq!(db.find_node::<Account>("a1").unwrap().unwrap().email, "a2@example.com");
+
+        db.upsert_node(&account("a2", "b@example.com", "bob")).unwrap();
+        assert!(matches!(
+            db.upsert_node(&account("a3", "b@example.com", "carol")),
+            Err(Error::ConstraintViolation { .. })
+        ));
+        assert!(db.find_node::<Account>("a3").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_create_and_drop_index() {
+        let (_dir, db) = setup_test_db();
+        db.create_node(&account("a1", "a@example.com", "same")).unwrap();
+        db.create_node(&account("a2", "b@example.com", "same")).unwrap();
+
+        // Existing duplicates prevent a unique index
+        assert!(db.create_index::<Account>("handle", IndexKind::Unique).is_err());
+        assert!(db.create_index::<Account>("missing", IndexKind::Unique).is_err());
+
+        db.update_node(&account("a2", "b@example.com", "other")).unwrap();
+        db.create_index::<Account>("handle", IndexKind::Unique).unwrap();
+        assert!(db.create_node(&account("a3", "c@example.com", "same")).is_err());
+
+        db.drop_index::<Account>("handle", IndexKind::Unique).unwrap();
+        db.create_node(&account("a3", "c@example.com", "same")).unwrap();
+        assert!(db.drop_index::<Account>("handle", IndexKind::Unique).is_err());
+        assert_eq!(db.list_indexes::<Account>().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,177 @@
+mod common;
+
+#[cfg(test)]
+mod key_tests {
+    use okodata::{Node, Relationship, KuzuGraph, QueryBuilder, Uuid};
+    use serde::{Serialize, Deserialize};
+    use tempfile::TempDir;
+    use crate::common;
+
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("Invoice")]
+    struct Invoice {
+        #[id]
+        number: i64,
+        #[property]
+        total: i64,
+    }
+
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("Session")]
+    struct Session {
+        #[id]
+        id: Uuid,
+        #[property]
+        user: String,
+    }
+
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("Membership")]
+    struct Membership {
+        #[id]
+        org: String,
+        #[id]
+        seat: i64,
+        #[property]
+        member: String,
+    }
+
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("Ticket")]
+    struct Ticket {
+        #[id(auto = "serial")]
+        id: i64,
+        #[property]
+        title: String,
+    }
+
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("Device")]
+    struct Device {
+        #[id(auto = "uuid")]
+        id: Uuid,
+        #[unique]
+        serial_number: String,
+    }
+
+    #[derive(Relationship, Debug, Serialize, Deserialize)]
+    #[label("BILLED")]
+    struct Billed {
+        #[from_node(Membership)]
+        seat: (String, i64),
+        #[to_node(Invoice)]
+        invoice: i64,
+        #[property]
+        since: i64,
+    }
+
+    fn setup_test_db() -> (TempDir, KuzuGraph) {
+        let (dir, db) = common::test_db();
+        db.create_node_table::<Invoice>().unwrap();
+        db.create_node_table::<Session>().unwrap();
+        db.create_node_table::<Membership>().unwrap();
+        db.create_node_table::<Ticket>().unwrap();
+        db.create_node_table::<Device>().unwrap();
+        db.create_relationship_table::<Billed>().unwrap();
+        (dir, db)
+    }
+
+    #[test]
+    fn test_int64_key() {
+        let (_dir, db) = setup_test_db();
+
+        db.create_node(&Invoice { number: 1001, total: 250 }).unwrap();
+        db.create_node(&Invoice { number: 1002, total: 75 }).unwrap();
+
+        let invoice = db.find_node::<Invoice>(1001).unwrap().unwrap();
+        assert_eq!(invoice.total, 250);
+        assert!(db.find_node::<Invoice>(1003).unwrap().is_none());
+
+        db.update_node(&Invoice { number: 1002, total: 80 }).unwrap();
+        assert_eq!(db.find_node::<Invoice>(1002).unwrap().unwrap().total, 80);
+    }
+
+    #[test]
+    fn test_uuid_key() {
+        let (_dir, db) = setup_test_db();
+        let id = Uuid::from_u128(0x1234);
+
+        db.upsert_node(&Session { id, user: "alice".to_string() }).unwrap();
+        let session = db.find_node::<Session>(id).unwrap().unwrap();
+        assert_eq!(session, Session { id, user: "alice".to_string() });
+        assert!(db.find_node::<Session>(Uuid::nil()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_composite_key() {
+        let (_dir, db) = setup_test_db();
+
+        db.create_node(&Membership { org: "acme".to_string(), seat: 1, member: "alice".to_string() }).unwrap();
+        db.create_node(&Membership { org: "acme".to_string(), seat: 2, member: "bob".to_string() }).unwrap();
+        db.create_node(&Membership { org: "initech".to_string(), seat: 1, member: "carol".to_string() }).unwrap();
+
+        let seat = db.find_node::<Membership>(("acme".to_string(), 2)).unwrap().unwrap();
+        assert_eq!(seat.member, "bob");
+
+        // Rewriting the same key parts replaces the node
+        db.create_node(&Membership { org: "acme".to_string(), seat: 2, member: "dave".to_string() }).unwrap();
+        assert_eq!(QueryBuilder::<Membership>::new().where_clause("n.org = 'acme'").count(&db).unwrap(), 2);
+        assert_eq!(
+            db.find_node::<Membership>(("acme".to_string(), 2)).unwrap().unwrap().member,
+            "dave"
+        );
+    }
+
+    #[test]
+    fn test_relationship_between_typed_keys() {
+        let (_dir, db) = setup_test_db();
+
+        db.create_node(&Membership { org: "acme".to_string(), seat: 1, member: "alice".to_string() }).unwrap();
+        db.create_node(&Membership { org: "acme".to_string(), seat: 2, member: "bob".to_string() }).unwrap();
+        db.create_node(&Invoice { number: 1001, total: 250 }).unwrap();
+        db.create_node(&Invoice { number: 1002, total: 75 }).unwrap();
+
+        db.create_relationship(&Billed { seat: ("acme".to_string(), 2), invoice: 1001, since: 2024 }).unwrap();
+
+        let billed = db.query_as::<(String, i64, i64)>(
+            "MATCH (m:Membership)-[r:BILLED]->(i:Invoice) RETURN m.member, i.number, r.since",
+            vec![],
+        ).unwrap();
+        assert_eq!(billed, vec![("bob".to_string(), 1001, 2024)]);
+    }
+
+    #[test]
+    fn test_serial_key() {
+        let (_dir, db) = setup_test_db();
+
+        let first = db.create_node(&Ticket { id: 0, title: "Broken build".to_string() }).unwrap();
+        let second = db.create_node(&Ticket { id: 0, title: "Flaky test".to_string() }).unwrap();
+        assert_eq!((first, second), (0, 1));
+
+        let ticket = db.find_node::<Ticket>(second).unwrap().unwrap();
+        assert_eq!(ticket.title, "Flaky test");
+
+        db.update_node(&Ticket { id: first, title: "Fixed build".to_string() }).unwrap();
+        assert_eq!(db.find_node::<Ticket>(first).unwrap().unwrap().title, "Fixed build");
+
+        // Generated keys are assigned by the database, so they cannot be merged on
+        assert!(db.upsert_node(&Ticket { id: 5, title: "Made up".to_string() }).is_err());
+        assert!(db.find_node::<Ticket>(5).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_generated_uuid_key() {
+        let (_dir, db) = setup_test_db();
+        let device = |serial: &str| Device { id: Uuid::nil(), serial_number: serial.to_string() };
+
+        let id = db.create_node(&device("SN-1")).unwrap();
+        assert_ne!(id, Uuid::nil());
+        assert_eq!(db.find_node::<Device>(id).unwrap().unwrap().serial_number, "SN-1");
+
+        let other = db.transaction(|txn| db.create_node_in_transaction(txn, &device("SN-2"))).unwrap();
+        assert_ne!(other, id);
+
+        // New nodes are checked against every existing node, whatever their placeholder key
+        assert!(db.create_node(&device("SN-1")).is_err());
+    }
+}
@@ -0,0 +1,144 @@
+mod common;
+
+#[cfg(test)]
+mod query_as_tests {
+    use okodata::{Node, Relationship, FromRow, KuzuGraph, Value};
+    use serde::{Serialize, Deserialize};
+    use tempfile::TempDir;
+    use crate::common;
+
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("User")]
+    struct User {
+        #[id]
+        id: String,
+        #[property]
+        name: String,
+        #[property]
+        age: u32,
+    }
+
+    #[derive(Relationship, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("FOLLOWS")]
+    struct Follows {
+        #[from_node]
+        from: String,
+        #[to_node]
+        to: String,
+        #[property]
+        since: u32,
+    }
+
+    #[derive(Relationship, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("BLOCKS")]
+    struct Blocks {
+        #[from_node]
+        blocker: String,
+        #[to_node]
+        blocked: String,
+        #[property]
+        reason: String,
+    }
+
+    #[derive(FromRow, Debug, PartialEq)]
+    struct FollowerCount {
+        name: String,
+        #[column("followers")]
+        count: i64,
+    }
+
+    #[derive(FromRow, Debug)]
+    struct Edge {
+        follower: User,
+        follows: Follows,
+    }
+
+    fn setup_test_db() -> (TempDir, KuzuGraph) {
+        let (dir, db) = common::test_db();
+        db.create_node_table::<User>().unwrap();
+        db.create_relationship_table::<Follows>().unwrap();
+
+        let alice = User { id: "u1".to_string(), name: "Alice".to_string(), age: 25 };
+        let bob = User { id: "u2".to_string(), name: "Bob".to_string(), age: 30 };
+        db.create_node(&alice).unwrap();
+        db.create_node(&bob).unwrap();
+        db.create_relationship(&Follows {
+            from: alice.id.clone(),
+            to: bob.id.clone(),
+            since: 2024,
+        }).unwrap();
+
+        (dir, db)
+    }
+
+    #[test]
+    fn test_query_as_struct_by_alias() {
+        let (_dir, db) = setup_test_db();
+
+        let counts: Vec<FollowerCount> = db.query_as(
+            "MATCH (a:User)-[:FOLLOWS]->(b:User) WHERE b.id = $id
+             RETURN b.name AS name, COUNT(*) AS followers",
+            vec![("id", Value::String("u2".to_string()))],
+        ).unwrap();
+
+        assert_eq!(counts, vec![FollowerCount { name: "Bob".to_string(), count: 1 }]);
+    }
+
+    #[test]
+    fn test_query_as_nested_node_and_relationship() {
+        let (_dir, db) = setup_test_db();
+
+        let edges: Vec<Edge> = db.query_as(
+            "MATCH (a:User)-[f:FOLLOWS]->(b:User) RETURN a AS follower, f AS follows, b",
+            vec![],
+        ).unwrap();
+
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].follower.name, "Alice");
+        assert_eq!(edges[0].follows.from, "u1");
+        assert_eq!(edges[0].follows.to, "u2");
+    }
+
+    #[test]
+    fn test_query_as_tuple() {
+        let (_dir, db) = setup_test_db();
+
+        let rows: Vec<(User, Follows, User)> = db.query_as(
+            "MATCH (a:User)-[f:FOLLOWS]->(b:User) RETURN a, f, b",
+            vec![],
+        ).unwrap();
+
+        let (from, follows, to) = &rows[0];
+        assert_eq!(from.id, "u1");
+        assert_eq!(follows.since, 2024);
+        assert_eq!(to.id, "u2");
+    }
+
+    #[test]
+    fn test_query_as_relationship_endpoint_fields() {
+        let (_dir, db) = setup_test_db();
+        db.create_relationship_table::<Blocks>().unwrap();
+        db.create_relationship(&Blocks {
+            blocker: "u2".to_string(),
+            blocked: "u1".to_string(),
+            reason: "spam".to_string(),
+        }).unwrap();
+
+        let rows: Vec<(User, Blocks, User)> = db.query_as(
+            "MATCH (a:User)-[r:BLOCKS]->(b:User) RETURN a, r, b",
+            vec![],
+        ).unwrap();
+        assert_eq!(rows[0].1, Blocks {
+            blocker: "u2".to_string(),
+            blocked: "u1".to_string(),
+            reason: "spam".to_string(),
+        });
+
+        // Endpoint keys cannot be filled in without the endpoint nodes
+        let result: okodata::Result<Vec<(Blocks, String)>> = db.query_as(
+            "MATCH (:User)-[r:BLOCKS]->(:User) RETURN r, r.reason",
+            vec![],
+        );
+        assert!(result.is_err());
+    }
+}
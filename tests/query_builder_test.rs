@@ -0,0 +1,212 @@
+mod common;
+
+#[cfg(test)]
+mod query_builder_tests {
+    use okodata::{Node, KuzuGraph, QueryBuilder, Order, Error};
+    use serde::{Serialize, Deserialize};
+    use tempfile::TempDir;
+    use crate::common;
+    use std::collections::HashMap;
+
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("Employee")]
+    struct Employee {
+        #[id]
+        id: String,
+        #[property]
+        dept: String,
+        #[property]
+        salary: i64,
+    }
+
+    // Methods named after fields do not clash with the generated `EmployeeFields`
+    impl Employee {
+        fn salary(&self) -> i64 {
+            self.salary
+        }
+    }
+
+    /// The first version of the `Task` table, before tasks had a priority
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("Task")]
+    struct TaskV1 {
+        #[id]
+        id: String,
+    }
+
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("Task")]
+    struct Task {
+        #[id]
+        id: String,
+        #[property]
+        #[serde(default)]
+        priority: i64,
+    }
+
+    fn setup_test_db() -> (TempDir, KuzuGraph) {
+        let (dir, db) = common::test_db();
+        db.create_node_table::<Employee>().unwrap();
+
+        for (id, dept, salary) in [
+            ("e1", "eng", 100),
+            ("e2", "eng", 200),
+            ("e3", "ops", 50),
+        ] {
+            db.create_node(&Employee {
+                id: id.to_string(),
+                dept: dept.to_string(),
+                salary,
+            }).unwrap();
+        }
+
+        (dir, db)
+    }
+
+    #[test]
+    fn test_aggregates() {
+        let (_dir, db) = setup_test_db();
+        let query = QueryBuilder::<Employee>::new();
+
+        assert_eq!(query.count(&db).unwrap(), 3);
+        assert_eq!(query.sum::<i64>(&db, "salary").unwrap(), 350);
+        assert_eq!(query.min::<i64>(&db, "salary").unwrap(), Some(50));
+        assert_eq!(query.max::<i64>(&db, "salary").unwrap(), Some(200));
+
+        let eng = QueryBuilder::<Employee>::new().where_clause("n.dept = 'eng'");
+        assert_eq!(eng.avg(&db, "salary").unwrap(), Some(150.0));
+
+        let mut ids = eng.collect::<String>(&db, "id").unwrap();
+        ids.sort();
+        assert_eq!(ids, vec!["e1", "e2"]);
+
+        let none = QueryBuilder::<Employee>::new().where_clause("n.salary > 1000");
+        assert_eq!(none.count(&db).unwrap(), 0);
+        assert_eq!(none.max::<i64>(&db, "salary").unwrap(), None);
+
+        // SKIP and LIMIT select the nodes that are aggregated
+        assert_eq!(QueryBuilder::<Employee>::new().limit(2).count(&db).unwrap(), 2);
+        assert_eq!(QueryBuilder::<Employee>::new().skip(1).count(&db).unwrap(), 2);
+        let top_two = QueryBuilder::<Employee>::new().order_by("salary", Order::Desc).limit(2);
+        assert_eq!(top_two.sum::<i64>(&db, "salary").unwrap(), 300);
+    }
+
+    #[test]
+    fn test_group_by() {
+        let (_dir, db) = setup_test_db();
+        let by_dept = QueryBuilder::<Employee>::new().group_by::<String>("dept");
+
+        let counts: HashMap<String, i64> = by_dept.count(&db).unwrap();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts["eng"], 2);
+        assert_eq!(counts["ops"], 1);
+
+        let totals = by_dept.sum::<i64>(&db, "salary").unwrap();
+        assert_eq!(totals["eng"], 300);
+        assert_eq!(totals["ops"], 50);
+
+        assert!(by_dept.avg(&db, "bonus").is_err());
+    }
+
+    #[test]
+    fn test_page_after() {
+        let (_dir, db) = setup_test_db();
+        let query = QueryBuilder::<Employee>::new().order_by("salary", Order::Asc);
+
+        let first = query.page_after(&db, None, 2).unwrap();
+        assert_eq!(
+            first.items.iter().map(|e| e.id.as_str()).collect::<Vec<_>>(),
+            vec!["e3", "e1"]
+        );
+
+        let cursor = first.next_cursor.expect("more results");
+        let second = query.page_after(&db, Some(&cursor), 2).unwrap();
+        assert_eq!(second.items.len(), 1);
+        assert_eq!(second.items[0].id, "e2");
+        assert_eq!(second.next_cursor, None);
+
+        assert!(query.page_after(&db, Some("bogus"), 2).is_err());
+    }
+
+    #[test]
+    fn test_order_and_select() {
+        let (_dir, db) = setup_test_db();
+
+        let rows = QueryBuilder::<Employee>::new()
+            .order_by("dept", Order::Asc)
+            .order_by("salary", Order::Desc)
+            .select((EmployeeFields::id, EmployeeFields::salary))
+            .fetch(&db)
+            .unwrap();
+        assert_eq!(rows, vec![
+            ("e2".to_string(), 200),
+            ("e1".to_string(), 100),
+            ("e3".to_string(), 50),
+        ]);
+
+        let top = QueryBuilder::<Employee>::new().order_by("salary", Order::Desc).limit(1).fetch(&db).unwrap();
+        assert_eq!(top[0].salary(), 200);
+
+        let depts = QueryBuilder::<Employee>::new()
+            .distinct()
+            .order_by("dept", Order::Desc)
+            .select(EmployeeFields::dept)
+            .fetch(&db)
+            .unwrap();
+        assert_eq!(depts, vec![("ops".to_string(),), ("eng".to_string(),)]);
+
+        // Distinct rows can only be ordered by the projected properties
+        let unprojected = QueryBuilder::<Employee>::new()
+            .distinct()
+            .order_by("salary", Order::Asc)
+            .select(EmployeeFields::dept)
+            .fetch(&db);
+        assert!(matches!(unprojected, Err(Error::Query(_))));
+    }
+
+    #[test]
+    fn test_page_after_descending() {
+        let (_dir, db) = setup_test_db();
+        let query = QueryBuilder::<Employee>::new().order_by("salary", Order::Desc);
+
+        let first = query.page_after(&db, None, 2).unwrap();
+        assert_eq!(first.items[0].id, "e2");
+        assert_eq!(first.items[1].id, "e1");
+
+        let second = query.page_after(&db, first.next_cursor.as_deref(), 2).unwrap();
+        assert_eq!(second.items.len(), 1);
+        assert_eq!(second.items[0].id, "e3");
+    }
+
+    #[test]
+    fn test_page_after_null_sort_keys() {
+        let (_dir, db) = setup_test_db();
+
+        // Tasks written before the priority column existed have a null priority
+        db.create_node_table::<TaskV1>().unwrap();
+        for id in ["t1", "t2", "t5"] {
+            db.create_node(&TaskV1 { id: id.to_string() }).unwrap();
+        }
+        db.create_node_table::<Task>().unwrap();
+        db.create_node(&Task { id: "t3".to_string(), priority: 1 }).unwrap();
+        db.create_node(&Task { id: "t4".to_string(), priority: 2 }).unwrap();
+
+        let pages = |order| {
+            let query = QueryBuilder::<Task>::new().order_by("priority", order);
+            let mut ids = Vec::new();
+            let mut cursor = None;
+            loop {
+                let page = query.page_after(&db, cursor.as_deref(), 2).unwrap();
+                ids.push(page.items.iter().map(|t| t.id.clone()).collect::<Vec<_>>());
+                match page.next_cursor {
+                    Some(next) => cursor = Some(next),
+                    None => return ids,
+                }
+            }
+        };
+
+        // Nulls sort last in ascending order and first in descending order
+        assert_eq!(pages(Order::Asc), vec![vec!["t3", "t4"], vec!["t1", "t2"], vec!["t5"]]);
+        assert_eq!(pages(Order::Desc), vec![vec!["t5", "t2"], vec!["t1", "t4"], vec!["t3"]]);
+    }
+}
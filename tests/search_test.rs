@@ -0,0 +1,87 @@
+mod common;
+
+#[cfg(test)]
+mod search_tests {
+    use okodata::{Node, KuzuGraph, Error};
+    use serde::{Serialize, Deserialize};
+    use tempfile::TempDir;
+    use crate::common;
+
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("Article")]
+    struct Article {
+        #[id]
+        id: String,
+        #[fulltext]
+        title: String,
+        #[property]
+        #[fulltext]
+        body: String,
+        #[property]
+        views: i64,
+    }
+
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("Tag")]
+    struct Tag {
+        #[id]
+        name: String,
+    }
+
+    fn setup_test_db() -> (TempDir, KuzuGraph) {
+        let (dir, db) = common::test_db();
+        db.init_schema::<Article>().unwrap();
+
+        for (id, title, body) in [
+            ("a1", "Graph databases", "Graphs model connected data"),
+            ("a2", "Rust ownership", "Borrowing rules in Rust"),
+            ("a3", "Rust and graph storage", "Embedding a graph database in Rust"),
+            ("a4", "Trusted sources", "Citing references"),
+        ] {
+            db.create_node(&Article {
+                id: id.to_string(),
+                title: title.to_string(),
+                body: body.to_string(),
+                views: 0,
+            }).unwrap();
+        }
+        (dir, db)
+    }
+
+    #[test]
+    fn test_fulltext_search() {
+        let (_dir, db) = setup_test_db();
+        assert_eq!(Article::fulltext_fields(), vec!["title", "body"]);
+
+        // Whole words only: "Trusted" does not match "rust"
+        let results = db.search::<Article>("RUST", 10).unwrap();
+        let mut ids: Vec<&str> = results.iter().map(|(a, _)| a.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["a2", "a3"]);
+        assert!(results.windows(2).all(|w| w[0].1 >= w[1].1));
+
+        // Nodes matching more of the keywords rank first
+        let top = db.search::<Article>("graph rust", 1).unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].0.id, "a3");
+        assert!(top[0].1 > 0.0);
+
+        // Nodes written later are found without rebuilding anything
+        db.create_node(&Article {
+            id: "a5".to_string(),
+            title: "Unrelated".to_string(),
+            body: "Nothing to see".to_string(),
+            views: 0,
+        }).unwrap();
+        assert_eq!(db.search::<Article>("unrelated", 10).unwrap()[0].0.id, "a5");
+        assert!(db.search::<Article>("missing", 10).unwrap().is_empty());
+        assert!(db.search::<Article>("  ", 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_search_without_fulltext_fields() {
+        let (_dir, db) = setup_test_db();
+        db.init_schema::<Tag>().unwrap();
+        assert!(matches!(db.search::<Tag>("rust", 10), Err(Error::Query(_))));
+    }
+}
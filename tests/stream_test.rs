@@ -0,0 +1,49 @@
+mod common;
+
+#[cfg(test)]
+mod stream_tests {
+    use okodata::{Node, KuzuGraph, Result};
+    use serde::{Serialize, Deserialize};
+    use tempfile::TempDir;
+    use crate::common;
+
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("Account")]
+    struct Account {
+        #[id]
+        id: String,
+        #[property]
+        balance: i64,
+    }
+
+    fn setup_test_db() -> (TempDir, KuzuGraph) {
+        let (dir, db) = common::test_db();
+        db.create_node_table::<Account>().unwrap();
+
+        for i in 0..100 {
+            let account = Account {
+                id: format!("acc{:03}", i),
+                balance: i,
+            };
+            db.create_node(&account).unwrap();
+        }
+
+        (dir, db)
+    }
+
+    #[test]
+    fn test_stream_nodes() {
+        let (_dir, db) = setup_test_db();
+
+        let mut stream = db
+            .stream::<Account>("MATCH (n:Account) RETURN n ORDER BY n.id")
+            .unwrap();
+
+        let first = stream.next().unwrap().unwrap();
+        assert_eq!(first.id, "acc000");
+
+        let rest = stream.collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(rest.len(), 99);
+        assert_eq!(rest.iter().map(|a| a.balance).sum::<i64>(), (1..100).sum::<i64>());
+    }
+}
@@ -0,0 +1,386 @@
+mod common;
+
+#[cfg(test)]
+mod traversal_tests {
+    use okodata::{Node, Relationship, KuzuGraph, Direction, Lazy, QueryBuilder, Order, Pattern, Cmp};
+    use serde::{Serialize, Deserialize};
+    use tempfile::TempDir;
+    use crate::common;
+
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("User")]
+    struct User {
+        #[id]
+        id: String,
+        #[property]
+        name: String,
+        #[property]
+        age: u32,
+    }
+
+    #[derive(Relationship, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("FOLLOWS")]
+    struct Follows {
+        #[from_node]
+        from: String,
+        #[to_node]
+        to: String,
+        #[property]
+        since: u32,
+    }
+
+    fn user(id: &str, name: &str) -> User {
+        User { id: id.to_string(), name: name.to_string(), age: 30 }
+    }
+
+    fn follows(from: &str, to: &str, since: u32) -> Follows {
+        Follows { from: from.to_string(), to: to.to_string(), since }
+    }
+
+    /// Alice follows Bob and Carol, Carol follows Alice
+    fn setup_test_db() -> (TempDir, KuzuGraph) {
+        let (dir, db) = common::test_db();
+        db.create_node_table::<User>().unwrap();
+        db.create_relationship_table::<Follows>().unwrap();
+
+        db.create_node(&user("u1", "Alice")).unwrap();
+        db.create_node(&user("u2", "Bob")).unwrap();
+        db.create_node(&user("u3", "Carol")).unwrap();
+        db.create_relationship(&follows("u1", "u2", 2020)).unwrap();
+        db.create_relationship(&follows("u1", "u3", 2021)).unwrap();
+        db.create_relationship(&follows("u3", "u1", 2022)).unwrap();
+
+        (dir, db)
+    }
+
+    #[test]
+    fn test_outgoing() {
+        let (_dir, db) = setup_test_db();
+
+        let mut following = db.outgoing::<Follows, User, User>("u1").unwrap();
+        following.sort_by(|a, b| a.1.id.cmp(&b.1.id));
+
+        assert_eq!(following.len(), 2);
+        assert_eq!(following[0].0, follows("u1", "u2", 2020));
+        assert_eq!(following[0].1.name, "Bob");
+        assert_eq!(following[1].1.name, "Carol");
+    }
+
+    #[test]
+    fn test_incoming() {
+        let (_dir, db) = setup_test_db();
+
+        let followers = db.incoming::<Follows, User, User>("u1").unwrap();
+
+        assert_eq!(followers.len(), 1);
+        assert_eq!(followers[0].0, follows("u3", "u1", 2022));
+        assert_eq!(followers[0].1.name, "Carol");
+    }
+
+    #[test]
+    fn test_neighbors_both() {
+        let (_dir, db) = setup_test_db();
+
+        let neighbors = db.neighbors::<Follows, User, User>("u1", Direction::Both).unwrap();
+        assert_eq!(neighbors.len(), 3);
+    }
+
+    #[test]
+    fn test_multi_hop_traversal() {
+        let (_dir, db) = setup_test_db();
+
+        let traversal = db.traverse::<User>("u1").via::<Follows>().hops(1..=2);
+        assert_eq!(
+            traversal.build("User"),
+            "MATCH (a:User)-[e:FOLLOWS*1..2]->(b:User) WHERE a.id = $id RETURN a, e, b"
+        );
+
+        let mut paths = traversal.collect::<User>().unwrap();
+        paths.sort_by_key(|p| p.len());
+
+        assert_eq!(paths.len(), 3);
+        let longest = &paths[2];
+        assert_eq!(longest.len(), 2);
+        let names: Vec<&str> = longest.nodes.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["Alice", "Carol", "Alice"]);
+        assert_eq!(longest.relationships[1], follows("u3", "u1", 2022));
+    }
+
+    #[test]
+    fn test_multi_hop_traversal_with_filters() {
+        let (_dir, db) = setup_test_db();
+
+        let paths = db.traverse::<User>("u1")
+            .via::<Follows>()
+            .hops(1..=2)
+            .where_relationship("r.since > 2020")
+            .collect::<User>()
+            .unwrap();
+
+        assert_eq!(paths.len(), 2);
+        assert!(paths.iter().all(|p| p.relationships.iter().all(|r| r.since > 2020)));
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        let (_dir, db) = setup_test_db();
+
+        let path = db.shortest_path::<Follows, User>("u3", "u2", 5).unwrap().unwrap();
+        assert_eq!(path.len(), 2);
+        assert_eq!(path.start().unwrap().name, "Carol");
+        assert_eq!(path.end().unwrap().name, "Bob");
+        assert_eq!(path.relationships[0], follows("u3", "u1", 2022));
+
+        let none = db.shortest_path::<Follows, User>("u2", "u1", 5).unwrap();
+        assert!(none.is_none());
+    }
+
+    #[test]
+    fn test_all_shortest_paths() {
+        let (_dir, db) = setup_test_db();
+
+        let paths = db.all_shortest_paths::<Follows, User>("u3", "u2", 5).unwrap();
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].nodes.len(), 3);
+    }
+
+    #[test]
+    fn test_weighted_shortest_path() {
+        let (_dir, db) = setup_test_db();
+
+        let weighted = db
+            .weighted_shortest_path::<Follows, User, _>("u3", "u2", |f| f64::from(f.since - 2000))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(weighted.weight, 42.0);
+        assert_eq!(weighted.path.len(), 2);
+        assert_eq!(weighted.path.end().unwrap().name, "Bob");
+    }
+
+    #[test]
+    fn test_weighted_shortest_path_by_field() {
+        let (_dir, db) = setup_test_db();
+
+        let weighted = db
+            .weighted_shortest_path_by_field::<Follows, User>("u3", "u2", "since")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(weighted.weight, 4042.0);
+        assert_eq!(weighted.path.relationships[1], follows("u1", "u2", 2020));
+
+        // Only numeric properties of the relationship can be used as weights
+        assert!(db.weighted_shortest_path_by_field::<Follows, User>("u3", "u2", "missing").is_err());
+    }
+
+    #[test]
+    fn test_eager_loading() {
+        let (_dir, db) = setup_test_db();
+
+        let loaded = QueryBuilder::<User>::new()
+            .order_by("id", Order::Asc)
+            .with::<Follows, User>()
+            .fetch(&db)
+            .unwrap();
+
+        assert_eq!(loaded.len(), 3);
+        assert_eq!(loaded[0].node.name, "Alice");
+        let mut following: Vec<&str> = loaded[0].related.iter().map(|(_, u)| u.name.as_str()).collect();
+        following.sort();
+        assert_eq!(following, vec!["Bob", "Carol"]);
+        assert!(loaded[0].related.iter().all(|(f, _)| f.from == "u1"));
+
+        assert_eq!(loaded[1].node.name, "Bob");
+        assert!(loaded[1].related.is_empty());
+
+        let followers = QueryBuilder::<User>::new()
+            .where_clause("n.id = 'u1'")
+            .with::<Follows, User>()
+            .direction(Direction::Incoming)
+            .fetch(&db)
+            .unwrap();
+        assert_eq!(followers.len(), 1);
+        assert_eq!(followers[0].related.len(), 1);
+        assert_eq!(followers[0].related[0].0, follows("u3", "u1", 2022));
+    }
+
+    /// A view of the User table with relation fields
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("User")]
+    struct Profile {
+        #[id]
+        id: String,
+        #[property]
+        name: String,
+        #[relation(Follows, direction = "out")]
+        following: Lazy<Vec<Profile>>,
+        #[relation(Follows, direction = "in")]
+        followers: Lazy<Vec<Profile>>,
+    }
+
+    #[test]
+    fn test_lazy_relations() {
+        let (_dir, db) = setup_test_db();
+
+        let alice = db.find_node::<Profile>("u1").unwrap().unwrap();
+        assert!(!alice.following.is_loaded());
+        assert!(alice.properties().get("following").is_none());
+
+        let mut following: Vec<&str> = alice.following
+            .load(&db)
+            .unwrap()
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        following.sort();
+        assert_eq!(following, vec!["Bob", "Carol"]);
+        assert!(alice.following.is_loaded());
+
+        let followers = alice.followers.load(&db).unwrap();
+        assert_eq!(followers.len(), 1);
+        assert_eq!(followers[0].name, "Carol");
+
+        let unbound = Profile {
+            id: "u4".to_string(),
+            name: "Dave".to_string(),
+            following: Lazy::default(),
+            followers: Lazy::default(),
+        };
+        assert!(unbound.following.load(&db).is_err());
+    }
+
+    #[test]
+    fn test_optional_match() {
+        let (_dir, db) = setup_test_db();
+
+        let rows = QueryBuilder::<User>::new()
+            .order_by("id", Order::Asc)
+            .optional::<Follows, User>()
+            .fetch(&db)
+            .unwrap();
+
+        // Alice follows two users, Bob none, Carol one
+        assert_eq!(rows.len(), 4);
+        let bob: Vec<_> = rows.iter().filter(|(u, _, _)| u.id == "u2").collect();
+        assert_eq!(bob.len(), 1);
+        assert_eq!(bob[0].1, None);
+        assert_eq!(bob[0].2, None);
+
+        let carol = rows.iter().find(|(u, _, _)| u.id == "u3").unwrap();
+        assert_eq!(carol.1, Some(follows("u3", "u1", 2022)));
+        assert_eq!(carol.2.as_ref().map(|u| u.name.as_str()), Some("Alice"));
+    }
+
+    #[test]
+    fn test_pattern_fetch() {
+        let (_dir, db) = setup_test_db();
+
+        let mut rows = Pattern::new()
+            .node::<User>("a")
+            .rel::<Follows>("f", Direction::Outgoing)
+            .node::<User>("b")
+            .filter("f", "since", Cmp::Ge, 2021)
+            .returns(&["a", "f", "b"])
+            .fetch::<(User, Follows, User)>(&db)
+            .unwrap();
+        rows.sort_by_key(|(_, f, _)| f.since);
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0], (user("u1", "Alice"), follows("u1", "u3", 2021), user("u3", "Carol")));
+        assert_eq!(rows[1].1, follows("u3", "u1", 2022));
+    }
+
+    #[test]
+    fn test_query_builder_stages_and_subqueries() {
+        let (_dir, db) = setup_test_db();
+        let following = || Pattern::new()
+            .node::<User>("n")
+            .rel::<Follows>("", Direction::Outgoing)
+            .node::<User>("");
+
+        // Users following someone, and users following exactly one person
+        let followers = QueryBuilder::<User>::new()
+            .exists(following())
+            .order_by("id", Order::Asc)
+            .fetch(&db)
+            .unwrap();
+        assert_eq!(followers, vec![user("u1", "Alice"), user("u3", "Carol")]);
+
+        let single = QueryBuilder::<User>::new()
+            .count_matches(following(), Cmp::Eq, 1)
+            .fetch(&db)
+            .unwrap();
+        assert_eq!(single, vec![user("u3", "Carol")]);
+
+        let mut names: Vec<String> = QueryBuilder::<User>::new()
+            .where_clause("n.id = 'u2'")
+            .union(QueryBuilder::<User>::new().count_matches(following(), Cmp::Eq, 2))
+            .fetch(&db)
+            .unwrap()
+            .into_iter()
+            .map(|u| u.name)
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["Alice", "Bob"]);
+
+        // Stage the two first users, then count who they follow
+        let counts = QueryBuilder::<User>::new()
+            .order_by("id", Order::Asc)
+            .limit(2)
+            .project(&["n"])
+            .node::<User>("n")
+            .rel::<Follows>("", Direction::Outgoing)
+            .node::<User>("m")
+            .returns(&["n.id", "COUNT(m)"])
+            .fetch::<(String, i64)>(&db)
+            .unwrap();
+        assert_eq!(counts, vec![("u1".to_string(), 2)]);
+    }
+
+    #[test]
+    fn test_pattern_stages_and_subqueries() {
+        let (_dir, db) = setup_test_db();
+
+        // Users followed by someone who follows at least one other user
+        let followed = Pattern::new()
+            .node::<User>("a")
+            .rel::<Follows>("", Direction::Outgoing)
+            .node::<User>("b")
+            .exists(
+                Pattern::new()
+                    .node::<User>("a")
+                    .rel::<Follows>("", Direction::Outgoing)
+                    .node::<User>("")
+            )
+            .with(&["b", "COUNT(a) AS followers"])
+            .where_clause("followers >= 1")
+            .returns(&["b.name", "followers"])
+            .fetch::<(String, i64)>(&db)
+            .unwrap();
+        assert_eq!(followed.len(), 3);
+
+        let names = Pattern::new()
+            .node::<User>("u")
+            .filter("u", "name", Cmp::Eq, "Alice")
+            .returns(&["u.name"])
+            .union(
+                Pattern::new()
+                    .node::<User>("u")
+                    .count(
+                        Pattern::new()
+                            .node::<User>("u")
+                            .rel::<Follows>("", Direction::Outgoing)
+                            .node::<User>(""),
+                        Cmp::Eq,
+                        0,
+                    )
+                    .returns(&["u.name"])
+            )
+            .fetch::<(String,)>(&db)
+            .unwrap();
+        let mut names: Vec<String> = names.into_iter().map(|(n,)| n).collect();
+        names.sort();
+        assert_eq!(names, vec!["Alice", "Bob"]);
+    }
+}
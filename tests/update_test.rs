@@ -0,0 +1,69 @@
+mod common;
+
+#[cfg(test)]
+mod update_tests {
+    use okodata::{Node, Relationship, KuzuGraph, Error};
+    use serde::{Serialize, Deserialize};
+    use tempfile::TempDir;
+    use crate::common;
+
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("User")]
+    struct User {
+        #[id]
+        id: String,
+        #[property]
+        name: String,
+        #[property]
+        age: u32,
+    }
+
+    #[derive(Relationship, Debug, Serialize, Deserialize)]
+    #[label("FOLLOWS")]
+    struct Follows {
+        #[from_node]
+        from: String,
+        #[to_node]
+        to: String,
+        #[property]
+        since: u32,
+    }
+
+    fn user(id: &str, name: &str, age: u32) -> User {
+        User { id: id.to_string(), name: name.to_string(), age }
+    }
+
+    fn setup_test_db() -> (TempDir, KuzuGraph) {
+        let (dir, db) = common::test_db();
+        db.create_node_table::<User>().unwrap();
+        db.create_relationship_table::<Follows>().unwrap();
+        (dir, db)
+    }
+
+    #[test]
+    fn test_update_node() {
+        let (_dir, db) = setup_test_db();
+        db.create_node(&user("u1", "Alice", 25)).unwrap();
+        db.create_node(&user("u2", "Bob", 30)).unwrap();
+        db.create_relationship(&Follows { from: "u1".to_string(), to: "u2".to_string(), since: 2020 }).unwrap();
+
+        db.update_node(&user("u1", "Alice", 26)).unwrap();
+        assert_eq!(db.find_node::<User>("u1").unwrap().unwrap(), user("u1", "Alice", 26));
+
+        // The node is updated in place, so its relationships are kept
+        let following = db.outgoing::<Follows, User, User>("u1").unwrap();
+        assert_eq!(following.len(), 1);
+        assert_eq!(following[0].1.name, "Bob");
+    }
+
+    #[test]
+    fn test_update_missing_node() {
+        let (_dir, db) = setup_test_db();
+
+        assert!(matches!(
+            db.update_node(&user("u9", "Nobody", 0)),
+            Err(Error::NodeNotFound(id)) if id == "u9"
+        ));
+        assert!(db.find_node::<User>("u9").unwrap().is_none());
+    }
+}
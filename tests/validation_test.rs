@@ -0,0 +1,120 @@
+mod common;
+
+#[cfg(test)]
+mod validation_tests {
+    use okodata::{Node, Relationship, KuzuGraph, Error};
+    use serde::{Serialize, Deserialize};
+    use tempfile::TempDir;
+    use crate::common;
+
+    fn not_reserved(name: &String) -> Result<(), String> {
+        if name == "admin" {
+            Err("is a reserved name".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("User")]
+    struct User {
+        #[id]
+        id: String,
+        #[property]
+        #[validate(length(min = 1, max = 16), custom = "not_reserved")]
+        name: String,
+        #[property]
+        #[validate(range(min = 0, max = 150))]
+        age: u32,
+        #[property]
+        #[validate(regex = "^[^@\\s]+@[^@\\s]+$")]
+        email: String,
+    }
+
+    #[derive(Relationship, Debug, Serialize, Deserialize)]
+    #[label("RATES")]
+    struct Rates {
+        #[from_node]
+        from: String,
+        #[to_node]
+        to: String,
+        #[property]
+        #[validate(range(min = 1, max = 5))]
+        stars: i64,
+    }
+
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("Reading")]
+    struct Reading {
+        #[id]
+        id: String,
+        #[property]
+        #[validate(range(min = -273.15, max = 1000))]
+        celsius: f64,
+    }
+
+    fn user(id: &str, name: &str, age: u32, email: &str) -> User {
+        User {
+            id: id.to_string(),
+            name: name.to_string(),
+            age,
+            email: email.to_string(),
+        }
+    }
+
+    fn setup_test_db() -> (TempDir, KuzuGraph) {
+        let (dir, db) = common::test_db();
+        db.create_node_table::<User>().unwrap();
+        db.create_relationship_table::<Rates>().unwrap();
+        (dir, db)
+    }
+
+    #[test]
+    fn test_validation_rules() {
+        assert!(user("u1", "alice", 30, "alice@example.com").validate().is_ok());
+
+        let errors = user("u1", "admin", 200, "not an email").validate().unwrap_err();
+        let failed: Vec<_> = errors.errors().iter().map(|e| (e.field.as_str(), e.rule)).collect();
+        assert_eq!(failed, vec![
+            ("name", "custom"),
+            ("age", "range"),
+            ("email", "regex"),
+        ]);
+
+        let errors = user("u1", "", 30, "a@b").validate().unwrap_err();
+        assert_eq!(errors.field("name")[0].rule, "length");
+
+        let rates = Rates { from: "u1".to_string(), to: "u2".to_string(), stars: 6 };
+        assert_eq!(rates.validate().unwrap_err().errors()[0].field, "stars");
+
+        let reading = |celsius| Reading { id: "r1".to_string(), celsius };
+        assert!(reading(-40.5).validate().is_ok());
+        assert!(reading(-273.5).validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_before_write() {
+        let (_dir, db) = setup_test_db();
+
+        match db.create_node(&user("u1", "alice", 151, "alice@example.com")) {
+            Err(Error::Validation(errors)) => assert_eq!(errors.errors().len(), 1),
+            other => panic!("Expected a validation error, got {:?}", other),
+        }
+        assert!(db.find_node::<User>("u1").unwrap().is_none());
+
+        db.create_node(&user("u1", "alice", 30, "alice@example.com")).unwrap();
+        db.create_node(&user("u2", "bob", 40, "bob@example.com")).unwrap();
+        assert!(matches!(
+            db.update_node(&user("u1", "alice", 30, "alice")),
+            Err(Error::Validation(_))
+        ));
+        assert!(matches!(
+            db.upsert_node(&user("u3", "admin", 30, "root@example.com")),
+            Err(Error::Validation(_))
+        ));
+        assert_eq!(db.find_node::<User>("u1").unwrap().unwrap().email, "alice@example.com");
+
+        let rates = Rates { from: "u1".to_string(), to: "u2".to_string(), stars: 0 };
+        assert!(matches!(db.create_relationship(&rates), Err(Error::Validation(_))));
+    }
+}
@@ -0,0 +1,70 @@
+mod common;
+
+#[cfg(test)]
+mod vector_tests {
+    use okodata::{Node, KuzuGraph, Metric, QueryBuilder};
+    use serde::{Serialize, Deserialize};
+    use tempfile::TempDir;
+    use crate::common;
+
+    #[derive(Node, Debug, PartialEq, Serialize, Deserialize)]
+    #[label("Document")]
+    struct Document {
+        #[id]
+        id: String,
+        #[property]
+        category: String,
+        #[vector(dim = 3)]
+        embedding: Vec<f32>,
+    }
+
+    fn doc(id: &str, category: &str, embedding: [f32; 3]) -> Document {
+        Document { id: id.to_string(), category: category.to_string(), embedding: embedding.to_vec() }
+    }
+
+    fn setup_test_db() -> (TempDir, KuzuGraph) {
+        let (dir, db) = common::test_db();
+        db.init_schema::<Document>().unwrap();
+
+        db.create_node(&doc("d1", "news", [1.0, 0.0, 0.0])).unwrap();
+        db.create_node(&doc("d2", "blog", [0.9, 0.1, 0.0])).unwrap();
+        db.create_node(&doc("d3", "news", [0.0, 1.0, 0.0])).unwrap();
+        (dir, db)
+    }
+
+    #[test]
+    fn test_vector_round_trip() {
+        let (_dir, db) = setup_test_db();
+
+        let found = db.find_node::<Document>("d2").unwrap().unwrap();
+        assert_eq!(found, doc("d2", "blog", [0.9, 0.1, 0.0]));
+
+        db.update_node(&doc("d2", "blog", [0.0, 0.0, 1.0])).unwrap();
+        let updated = db.find_node::<Document>("d2").unwrap().unwrap();
+        assert_eq!(updated.embedding, vec![0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_nearest() {
+        let (_dir, db) = setup_test_db();
+
+        let results = db.nearest::<Document>("embedding", &[1.0, 0.0, 0.0], 2, Metric::Cosine).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0.id, "d1");
+        assert_eq!(results[1].0.id, "d2");
+        assert!(results[0].1 >= results[1].1);
+
+        let closest = db.nearest::<Document>("embedding", &[0.0, 1.0, 0.0], 1, Metric::Euclidean).unwrap();
+        assert_eq!(closest[0].0.id, "d3");
+        assert!(closest[0].1.abs() < 1e-6);
+
+        let news = QueryBuilder::<Document>::new()
+            .where_clause("n.category = 'news'")
+            .nearest(&db, "embedding", &[0.9, 0.1, 0.0], 1, Metric::InnerProduct)
+            .unwrap();
+        assert_eq!(news[0].0.id, "d1");
+
+        assert!(db.nearest::<Document>("embedding", &[1.0, 0.0], 1, Metric::Cosine).is_err());
+        assert!(db.nearest::<Document>("category", &[1.0, 0.0, 0.0], 1, Metric::Cosine).is_err());
+    }
+}